@@ -0,0 +1,197 @@
+use std::convert::TryFrom;
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use super::user_action;
+use crate::{
+	actor_system::Ctx,
+	commands::{targets, PLAY_TYPES},
+	entry::{Entry, EntryIdentifier},
+	models::{ControlRequest, EntryUpdate, PageType, RSState, UIMode, UserAction},
+};
+
+// A request that changed something in PulseAudio, answered once the
+// changed entries come back.
+pub struct PendingReply {
+	idents: Vec<EntryIdentifier>,
+	waiting: Vec<EntryIdentifier>,
+	reply: mpsc::UnboundedSender<String>,
+}
+
+#[derive(Serialize)]
+struct Reply<'a> {
+	ok: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+	page: &'static str,
+	server: usize,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	selected: Option<&'a Entry>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	entries: Vec<&'a Entry>,
+}
+
+// `<action> [target]`, where action is written like in key bindings and the
+// target is an index, name or regex of a sink, source or stream; `state` only replies
+pub fn handle(
+	msg: &ControlRequest,
+	state: &mut RSState,
+	ctx: &Ctx,
+	pending: &mut Vec<PendingReply>,
+) {
+	pending.retain(|p| !p.reply.is_closed());
+
+	let request = msg.request.trim();
+	if request == "state" {
+		let _ = msg.reply.send(reply(state, &[], None));
+		return;
+	}
+
+	let (action, target) = match request.split_once(char::is_whitespace) {
+		Some((action, target)) => (action, Some(target.trim())),
+		None => (request, None),
+	};
+
+	let action = match UserAction::try_from(action.to_string()) {
+		Ok(action) => action,
+		Err(err) => {
+			let _ = msg.reply.send(reply(state, &[], Some(err.to_string())));
+			return;
+		}
+	};
+
+	let idents = match target {
+		Some(target) => match targeted(&action, target, state) {
+			Ok(idents) => idents,
+			Err(err) => {
+				let _ = msg.reply.send(reply(state, &[], Some(err)));
+				return;
+			}
+		},
+		None => {
+			let affected = affected_entry(&action, state);
+			user_action::handle(&action, state, ctx);
+
+			affected.into_iter().collect()
+		}
+	};
+
+	if idents.is_empty() {
+		let _ = msg.reply.send(reply(state, &[], None));
+	} else {
+		pending.push(PendingReply {
+			waiting: idents.clone(),
+			idents,
+			reply: msg.reply.clone(),
+		});
+	}
+}
+
+pub fn on_update(msg: &EntryUpdate, state: &RSState, pending: &mut Vec<PendingReply>) {
+	let ident = match msg {
+		EntryUpdate::EntryUpdate(ident, _) | EntryUpdate::EntryRemoved(ident) => ident,
+		_ => {
+			return;
+		}
+	};
+
+	pending.retain_mut(|p| {
+		p.waiting.retain(|i| i != ident);
+		if !p.waiting.is_empty() {
+			return true;
+		}
+
+		let _ = p.reply.send(reply(state, &p.idents, None));
+		false
+	});
+}
+
+// applies the action to every matching entry of the current server
+fn targeted(
+	action: &UserAction,
+	target: &str,
+	state: &mut RSState,
+) -> Result<Vec<EntryIdentifier>, String> {
+	if !matches!(
+		action,
+		UserAction::RequestMute(_)
+			| UserAction::RequstChangeVolume(_, _)
+			| UserAction::RequestChangeBalance(_, _)
+			| UserAction::CenterBalance(_)
+	) {
+		return Err(format!("'{}' doesn't take a target", action.to_string()));
+	}
+
+	let server = state.current_server;
+	let entries = PLAY_TYPES
+		.iter()
+		.flat_map(|t| state.entries.iter_type(*t))
+		.filter(|(ident, _)| ident.server == server)
+		.map(|(_, entry)| entry);
+
+	let idents: Vec<EntryIdentifier> = targets::matching_entries(entries, &PLAY_TYPES, target)
+		.map_err(|err| err.to_string())?
+		.iter()
+		.map(|entry| entry.entry_ident)
+		.collect();
+
+	for ident in &idents {
+		let ident = Some(*ident);
+
+		match action {
+			UserAction::RequestMute(_) => state.request_mute(&ident),
+			UserAction::RequstChangeVolume(how_much, _) => {
+				state.request_change_volume(*how_much, &ident)
+			}
+			UserAction::RequestChangeBalance(how_much, _) => {
+				state.request_change_balance(*how_much, &ident)
+			}
+			UserAction::CenterBalance(_) => state.request_center_balance(&ident),
+			_ => {}
+		}
+	}
+
+	Ok(idents)
+}
+
+// the entry a PulseAudio update is expected for, checked the same way as in `user_action`
+fn affected_entry(action: &UserAction, state: &RSState) -> Option<EntryIdentifier> {
+	if state.ui_mode != UIMode::Normal {
+		return None;
+	}
+
+	let waits = match action {
+		UserAction::RequestMute(_)
+		| UserAction::RequstChangeVolume(_, _)
+		| UserAction::RequestChangeBalance(_, _)
+		| UserAction::CenterBalance(_) => state.current_page.has_play_entries(),
+		UserAction::CycleProfiles(_) => state.current_page == PageType::Cards,
+		_ => false,
+	};
+
+	if waits {
+		state.page_entries.get_selected()
+	} else {
+		None
+	}
+}
+
+fn reply(state: &RSState, idents: &[EntryIdentifier], error: Option<String>) -> String {
+	let reply = Reply {
+		ok: error.is_none(),
+		error,
+		page: state.current_page.as_str(),
+		server: state.current_server,
+		selected: state
+			.page_entries
+			.get_selected()
+			.and_then(|ident| state.entries.get(&ident)),
+		entries: idents
+			.iter()
+			.filter_map(|ident| state.entries.get(ident))
+			.collect(),
+	};
+
+	serde_json::to_string(&reply).unwrap_or_default()
+}
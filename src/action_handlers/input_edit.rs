@@ -0,0 +1,76 @@
+// Editing shared by the text inputs. Cursors count chars, not bytes, and
+// every function returns the new value and cursor.
+
+pub fn add_char(value: &str, cursor: usize, c: char, max_len: usize) -> (String, usize) {
+	let mut chars: Vec<char> = value.chars().collect();
+
+	if chars.len() >= max_len || cursor > chars.len() {
+		return (value.to_string(), cursor);
+	}
+
+	chars.insert(cursor, c);
+
+	(chars.into_iter().collect(), cursor + 1)
+}
+
+pub fn remove_char(value: &str, cursor: usize) -> (String, usize) {
+	if cursor == 0 || cursor > value.chars().count() {
+		return (value.to_string(), cursor);
+	}
+
+	let value = value
+		.chars()
+		.enumerate()
+		.filter(|(i, _)| *i != cursor - 1)
+		.map(|(_, c)| c)
+		.collect();
+
+	(value, cursor - 1)
+}
+
+pub fn move_cursor(value: &str, cursor: usize, by: isize) -> (String, usize) {
+	let moved = cursor
+		.checked_add_signed(by)
+		.filter(|c| *c <= value.chars().count())
+		.unwrap_or(cursor);
+
+	(value.to_string(), moved)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn edits_at_the_cursor() {
+		assert_eq!(add_char("ac", 1, 'b', 10), ("abc".to_string(), 2));
+		assert_eq!(remove_char("abc", 2), ("ac".to_string(), 1));
+		assert_eq!(remove_char("abc", 0), ("abc".to_string(), 0));
+	}
+
+	#[test]
+	fn counts_chars_not_bytes() {
+		assert_eq!(add_char("żółw", 4, '!', 10), ("żółw!".to_string(), 5));
+		assert_eq!(remove_char("żółw", 2), ("żłw".to_string(), 1));
+	}
+
+	#[test]
+	fn stops_at_max_len() {
+		assert_eq!(add_char("abc", 3, 'd', 3), ("abc".to_string(), 3));
+	}
+
+	#[test]
+	fn cursor_stays_inside_the_value() {
+		assert_eq!(move_cursor("abc", 0, -1), ("abc".to_string(), 0));
+		assert_eq!(move_cursor("abc", 3, 1), ("abc".to_string(), 3));
+		assert_eq!(move_cursor("abc", 1, 1), ("abc".to_string(), 2));
+	}
+
+	#[test]
+	fn long_values_keep_their_cursor() {
+		let value = "a".repeat(300);
+
+		assert_eq!(move_cursor(&value, 255, 1).1, 256);
+		assert_eq!(add_char(&value, 256, 'b', usize::MAX).1, 257);
+	}
+}
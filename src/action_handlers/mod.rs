@@ -1,5 +1,8 @@
+pub mod control;
+mod input_edit;
 pub mod pulseaudio_info;
 pub mod pulseaudio_status;
+pub mod text_input_edit;
 pub mod user_action;
 pub mod user_input;
 pub mod volume_input_edit;
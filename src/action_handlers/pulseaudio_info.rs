@@ -10,8 +10,13 @@ pub fn handle(msg: &EntryUpdate, state: &mut RSState) {
 		EntryUpdate::EntryRemoved(ident) => {
 			state.remove_entry(ident);
 		}
-		EntryUpdate::PeakVolumeUpdate(ident, peak) => {
-			state.update_peak_volume(ident, peak);
+		EntryUpdate::PeakVolumeUpdates(levels) => {
+			for (ident, levels) in levels {
+				state.update_peak_volume(ident, levels);
+			}
+		}
+		EntryUpdate::ServerInfo(server_info) => {
+			state.update_server_info(server_info.clone());
 		}
 	}
 }
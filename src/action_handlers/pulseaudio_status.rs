@@ -1,15 +1,15 @@
-use crate::models::{PAStatus, RSState, UIMode};
+use crate::models::{PAStatus, RSState};
 
 pub fn handle(msg: &PAStatus, state: &mut RSState) {
 	match msg {
-		PAStatus::PulseAudioDisconnected => {
-			state.reset();
+		PAStatus::PulseAudioDisconnected(server) => {
+			state.server_disconnected(*server);
 		}
-		PAStatus::RetryIn(time) => {
-			state.change_ui_mode(UIMode::RetryIn(*time));
+		PAStatus::RetryIn(server, time) => {
+			state.server_retry_in(*server, *time);
 		}
-		PAStatus::ConnectToPulseAudio => {
-			state.change_ui_mode(UIMode::Normal);
+		PAStatus::ConnectToPulseAudio(server) => {
+			state.server_connected(*server);
 		}
 	}
 }
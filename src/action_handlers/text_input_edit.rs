@@ -0,0 +1,41 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::input_edit::{add_char, move_cursor, remove_char};
+use crate::models::{RSState, UserAction};
+
+pub fn handle(actions: &mut Vec<UserAction>, input: &KeyEvent, state: &RSState) -> Result<()> {
+	// every printable key goes into the text, so bindings on chars
+	// (like `q` closing the window) don't apply here
+	let (value, cursor) = (&state.input_text.value, state.input_text.cursor);
+	let new_input_value = match input.code {
+		KeyCode::Char(x) if (input.modifiers - KeyModifiers::SHIFT).is_empty() => {
+			Some(add_char(value, cursor, x, usize::MAX))
+		}
+		KeyCode::Backspace => Some(remove_char(value, cursor)),
+		KeyCode::Left => Some(move_cursor(value, cursor, -1)),
+		KeyCode::Right => Some(move_cursor(value, cursor, 1)),
+		KeyCode::Enter => {
+			actions.clear();
+			actions.push(UserAction::Confirm);
+			return Ok(());
+		}
+		KeyCode::Esc => {
+			actions.clear();
+			actions.push(UserAction::CloseContextMenu);
+			return Ok(());
+		}
+		_ => None,
+	};
+
+	match new_input_value {
+		Some((value, cursor)) => {
+			actions.clear();
+			actions.push(UserAction::ChangeTextInputValue(value, cursor));
+		}
+		// only quitting still works while typing
+		None => actions.retain(|a| *a == UserAction::RequestQuit),
+	}
+
+	Ok(())
+}
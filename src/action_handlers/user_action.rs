@@ -1,5 +1,6 @@
 use crate::{
 	actor_system::Ctx,
+	actors::pulse_actor_id,
 	models::{PageType, PulseAudioAction, RSState, UIMode, UserAction},
 };
 
@@ -29,27 +30,58 @@ pub fn handle(msg: &UserAction, state: &mut RSState, ctx: &Ctx) {
 				UserAction::ChangePage(PageType::from(i8::from(state.current_page) + which_way)),
 			);
 		}
+		UserAction::CycleServers(which_way) => {
+			if let UIMode::Normal | UIMode::RetryIn(_) = state.ui_mode {
+				state.cycle_servers(*which_way);
+			}
+		}
+		UserAction::CycleProfiles(which_way) => {
+			if state.ui_mode == UIMode::Normal && state.current_page == PageType::Cards {
+				state.cycle_profiles(*which_way);
+			}
+		}
 		UserAction::RequestMute(ident) => {
-			if state.ui_mode != UIMode::Normal || state.current_page == PageType::Cards {
+			if state.ui_mode != UIMode::Normal || !state.current_page.has_play_entries() {
 				return;
 			}
 
 			state.request_mute(ident);
 		}
 		UserAction::RequstChangeVolume(how_much, ident) => {
-			if state.ui_mode != UIMode::Normal || state.current_page == PageType::Cards {
+			if state.ui_mode != UIMode::Normal || !state.current_page.has_play_entries() {
 				return;
 			}
 
 			state.request_change_volume(*how_much, ident);
 		}
+		UserAction::RequestChangeBalance(how_much, ident) => {
+			if state.ui_mode != UIMode::Normal || !state.current_page.has_play_entries() {
+				return;
+			}
+
+			state.request_change_balance(*how_much, ident);
+		}
+		UserAction::CenterBalance(ident) => {
+			if state.ui_mode != UIMode::Normal || !state.current_page.has_play_entries() {
+				return;
+			}
+
+			state.request_center_balance(ident);
+		}
 		UserAction::OpenContextMenu(ident) => {
 			if state.ui_mode == UIMode::Normal {
 				state.open_context_menu(ident);
 			}
 		}
 		UserAction::CloseContextMenu => {
-			if let UIMode::ContextMenu | UIMode::Help | UIMode::InputVolumeValue = state.ui_mode {
+			if let UIMode::ContextMenu
+			| UIMode::Help
+			| UIMode::InputVolumeValue
+			| UIMode::InputLatencyOffset(_, _)
+			| UIMode::InputModule
+			| UIMode::ServerInfo
+			| UIMode::Details(_) = state.ui_mode
+			{
 				state.change_ui_mode(UIMode::Normal);
 			}
 		}
@@ -60,7 +92,7 @@ pub fn handle(msg: &UserAction, state: &mut RSState, ctx: &Ctx) {
 			UIMode::MoveEntry(ident, parent) => {
 				state.change_ui_mode(UIMode::Normal);
 				ctx.send_to(
-					"pulseaudio",
+					pulse_actor_id(ident.server),
 					PulseAudioAction::MoveEntryToParent(ident, parent),
 				);
 			}
@@ -68,6 +100,14 @@ pub fn handle(msg: &UserAction, state: &mut RSState, ctx: &Ctx) {
 				state.confirm_input_volume();
 				state.change_ui_mode(UIMode::Normal);
 			}
+			UIMode::InputLatencyOffset(ident, port) => {
+				state.confirm_latency_input(ident, port);
+				state.change_ui_mode(UIMode::Normal);
+			}
+			UIMode::InputModule => {
+				state.confirm_module_input();
+				state.change_ui_mode(UIMode::Normal);
+			}
 			_ => {}
 		},
 		UserAction::Hide(ident) => {
@@ -80,11 +120,16 @@ pub fn handle(msg: &UserAction, state: &mut RSState, ctx: &Ctx) {
 				state.change_ui_mode(UIMode::Help);
 			}
 		}
+		UserAction::ShowServerInfo => {
+			if UIMode::Normal == state.ui_mode {
+				state.show_server_info();
+			}
+		}
 		UserAction::RequestQuit => {
 			ctx.shutdown();
 		}
 		UserAction::InputVolumeValue => {
-			if UIMode::Normal == state.ui_mode && state.current_page != PageType::Cards {
+			if UIMode::Normal == state.ui_mode && state.current_page.has_play_entries() {
 				state.setup_volume_input();
 				state.change_ui_mode(UIMode::InputVolumeValue);
 			}
@@ -92,5 +137,21 @@ pub fn handle(msg: &UserAction, state: &mut RSState, ctx: &Ctx) {
 		UserAction::ChangeVolumeInputValue(value, cursor) => {
 			state.set_volume_input_value(value.clone(), *cursor);
 		}
+		UserAction::LoadModule => {
+			if UIMode::Normal == state.ui_mode && state.current_page == PageType::Modules {
+				state.setup_module_input();
+				state.change_ui_mode(UIMode::InputModule);
+			}
+		}
+		UserAction::ChangeTextInputValue(value, cursor) => {
+			state.set_text_input_value(value.clone(), *cursor);
+		}
+		UserAction::ToggleChannels(ident) => {
+			if state.ui_mode != UIMode::Normal || !state.current_page.has_play_entries() {
+				return;
+			}
+
+			state.toggle_channels(ident);
+		}
 	}
 }
@@ -3,10 +3,10 @@ use std::convert::TryFrom;
 use anyhow::Result;
 use crossterm::event::{Event, MouseButton, MouseEvent, MouseEventKind};
 
-use super::volume_input_edit;
+use super::{text_input_edit, volume_input_edit};
 use crate::{
 	actor_system::Ctx,
-	entry::{EntryIdentifier, EntryKind},
+	entry::EntryIdentifier,
 	models::{InputEvent, PageType, RSState, UIMode, UserAction, UserInput},
 	ui::{Rect, Scrollable},
 	BINDINGS,
@@ -32,12 +32,18 @@ pub fn handle(input: &UserInput, state: &RSState, ctx: &Ctx) -> Result<()> {
 		}
 	}
 
-	if state.ui_mode == UIMode::InputVolumeValue {
+	if let UIMode::InputVolumeValue | UIMode::InputLatencyOffset(_, _) = state.ui_mode {
 		if let Event::Key(key_event) = input.event {
 			volume_input_edit::handle(&mut actions, &key_event, state)?;
 		}
 	}
 
+	if state.ui_mode == UIMode::InputModule {
+		if let Event::Key(key_event) = input.event {
+			text_input_edit::handle(&mut actions, &key_event, state)?;
+		}
+	}
+
 	for action in actions {
 		ctx.send_to("event_loop", action);
 	}
@@ -138,13 +144,10 @@ fn find_collisions(
 			.filter_map(|i| state.page_entries.get(i))
 			.filter_map(|ident| state.entries.get(&ident))
 		{
-			let area = match &entry.entry_kind {
-				EntryKind::CardEntry(card) => card.area,
-				EntryKind::PlayEntry(play) => play.area,
-			};
+			let area = entry.area();
 
 			if area.intersects(&mouse_event_rect) {
-				ident = Some(EntryIdentifier::new(entry.entry_type, entry.index));
+				ident = Some(entry.entry_ident);
 				break;
 			}
 		}
@@ -173,6 +176,12 @@ fn handle_mouse_bindings(actions: &mut Vec<UserAction>, mouse_event: MouseEvent,
 			UserAction::RequstChangeVolume(value, _) => {
 				*a = UserAction::RequstChangeVolume(*value, ident);
 			}
+			UserAction::RequestChangeBalance(value, _) => {
+				*a = UserAction::RequestChangeBalance(*value, ident);
+			}
+			UserAction::CenterBalance(_) => {
+				*a = UserAction::CenterBalance(ident);
+			}
 			UserAction::RequestMute(_) => {
 				*a = UserAction::RequestMute(ident);
 			}
@@ -182,6 +191,9 @@ fn handle_mouse_bindings(actions: &mut Vec<UserAction>, mouse_event: MouseEvent,
 			UserAction::Hide(_) => {
 				*a = UserAction::Hide(ident);
 			}
+			UserAction::ToggleChannels(_) => {
+				*a = UserAction::ToggleChannels(ident);
+			}
 			_ => {}
 		}
 	}
@@ -197,6 +209,10 @@ fn handle_conflicting_bindings(actions: &mut Vec<UserAction>, state: &RSState) {
 		if let UIMode::ContextMenu
 		| UIMode::Help
 		| UIMode::InputVolumeValue
+		| UIMode::InputLatencyOffset(_, _)
+		| UIMode::InputModule
+		| UIMode::ServerInfo
+		| UIMode::Details(_)
 		| UIMode::MoveEntry(_, _) = state.ui_mode
 		{
 			actions.retain(|action| *action != UserAction::RequestQuit);
@@ -208,8 +224,10 @@ fn handle_conflicting_bindings(actions: &mut Vec<UserAction>, state: &RSState) {
 	if actions.contains(&UserAction::Confirm)
 		&& actions.contains(&UserAction::OpenContextMenu(None))
 	{
-		if let UIMode::MoveEntry(_, _) | UIMode::ContextMenu | UIMode::InputVolumeValue =
-			state.ui_mode
+		if let UIMode::MoveEntry(_, _)
+		| UIMode::ContextMenu
+		| UIMode::InputVolumeValue
+		| UIMode::InputLatencyOffset(_, _) = state.ui_mode
 		{
 			actions.retain(|action| *action != UserAction::OpenContextMenu(None));
 		} else {
@@ -217,8 +235,16 @@ fn handle_conflicting_bindings(actions: &mut Vec<UserAction>, state: &RSState) {
 		}
 	}
 
+	let channels_expanded = state.ui_mode == UIMode::Normal
+		&& state
+			.page_entries
+			.get_selected()
+			.and_then(|ident| state.entries.get_play_entry(&ident))
+			.map(|play| play.expanded)
+			.unwrap_or(false);
+
 	if actions.contains(&UserAction::MoveLeft) {
-		if let UIMode::ContextMenu | UIMode::Help = state.ui_mode {
+		if matches!(state.ui_mode, UIMode::ContextMenu | UIMode::Help) || channels_expanded {
 			actions.retain(|action| *action == UserAction::MoveLeft);
 		} else {
 			actions.retain(|action| *action != UserAction::MoveLeft);
@@ -226,7 +252,7 @@ fn handle_conflicting_bindings(actions: &mut Vec<UserAction>, state: &RSState) {
 	}
 
 	if actions.contains(&UserAction::MoveRight) {
-		if let UIMode::ContextMenu | UIMode::Help = state.ui_mode {
+		if matches!(state.ui_mode, UIMode::ContextMenu | UIMode::Help) || channels_expanded {
 			actions.retain(|action| *action == UserAction::MoveRight);
 		} else {
 			actions.retain(|action| *action != UserAction::MoveRight);
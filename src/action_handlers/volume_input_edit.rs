@@ -1,8 +1,12 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 
+use super::input_edit::{add_char, move_cursor, remove_char};
 use crate::models::{RSState, UserAction};
 
+// enough for values like `-12.5dB`
+const MAX_INPUT_LEN: usize = 7;
+
 pub fn handle(actions: &mut Vec<UserAction>, input: &KeyEvent, state: &RSState) -> Result<()> {
 	let confirm = actions.contains(&UserAction::Confirm);
 	let close_context_menu = actions.contains(&UserAction::CloseContextMenu);
@@ -18,6 +22,10 @@ pub fn handle(actions: &mut Vec<UserAction>, input: &KeyEvent, state: &RSState)
 		return Ok(());
 	}
 
+	let (value, cursor) = (
+		&state.input_exact_volume.value,
+		state.input_exact_volume.cursor,
+	);
 	let new_input_value = match input.code {
 		KeyCode::Char(x @ '0')
 		| KeyCode::Char(x @ '1')
@@ -28,10 +36,18 @@ pub fn handle(actions: &mut Vec<UserAction>, input: &KeyEvent, state: &RSState)
 		| KeyCode::Char(x @ '6')
 		| KeyCode::Char(x @ '7')
 		| KeyCode::Char(x @ '8')
-		| KeyCode::Char(x @ '9') => Some(add_char(x, state)),
-		KeyCode::Backspace => Some(remove_char(state)),
-		KeyCode::Left => Some(move_cursor(state, -1)),
-		KeyCode::Right => Some(move_cursor(state, 1)),
+		| KeyCode::Char(x @ '9')
+		| KeyCode::Char(x @ '+')
+		| KeyCode::Char(x @ '-')
+		| KeyCode::Char(x @ '.')
+		| KeyCode::Char(x @ '%')
+		| KeyCode::Char(x @ 'd')
+		| KeyCode::Char(x @ 'D')
+		| KeyCode::Char(x @ 'b')
+		| KeyCode::Char(x @ 'B') => Some(add_char(value, cursor, x, MAX_INPUT_LEN)),
+		KeyCode::Backspace => Some(remove_char(value, cursor)),
+		KeyCode::Left => Some(move_cursor(value, cursor, -1)),
+		KeyCode::Right => Some(move_cursor(value, cursor, 1)),
 		_ => None,
 	};
 
@@ -42,40 +58,3 @@ pub fn handle(actions: &mut Vec<UserAction>, input: &KeyEvent, state: &RSState)
 
 	Ok(())
 }
-
-fn remove_char(state: &RSState) -> (String, u8) {
-	let value = state.input_exact_volume.value.clone();
-	let cursor = state.input_exact_volume.cursor as usize;
-
-	if cursor == 0 {
-		(value, cursor as u8)
-	} else {
-		let value = format!("{}{}", &value[0..(cursor - 1)], &value[cursor..]);
-
-		(value, cursor as u8 - 1)
-	}
-}
-
-fn move_cursor(state: &RSState, val: i8) -> (String, u8) {
-	let value = state.input_exact_volume.value.clone();
-	let cursor = state.input_exact_volume.cursor as i8;
-
-	if cursor < -val || cursor > value.len() as i8 - val {
-		(value, cursor as u8)
-	} else {
-		(value, (cursor + val) as u8)
-	}
-}
-
-fn add_char(c: char, state: &RSState) -> (String, u8) {
-	let value = state.input_exact_volume.value.clone();
-	let cursor = state.input_exact_volume.cursor as usize;
-
-	if value.len() == 3 {
-		(value, cursor as u8)
-	} else {
-		let value = format!("{}{}{}", &value[0..cursor], c, &value[cursor..]);
-
-		(value, cursor as u8 + 1)
-	}
-}
@@ -7,7 +7,7 @@ use tokio::{
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 
 use super::super::{
-	actor::{Actor, ActorType, BoxedResultFuture, LockedActor},
+	actor::{Actor, ActorId, ActorType, BoxedResultFuture, LockedActor},
 	channel,
 	context::Ctx,
 	messages::{BoxedMessage, Shutdown},
@@ -47,7 +47,7 @@ impl ActorInstance {
 		self.events_rx.write().await
 	}
 
-	pub async fn start_actor_task(&mut self, id: &'static str, actor_type: ActorType, ctx: Ctx) {
+	pub async fn start_actor_task(&mut self, id: ActorId, actor_type: ActorType, ctx: Ctx) {
 		let event_loop = match actor_type {
 			ActorType::Continous => {
 				let mut actor = self.actor.write().await;
@@ -77,11 +77,7 @@ impl ActorInstance {
 	}
 }
 
-fn generate_actor_result_handler(
-	id: &'static str,
-	ctx: Ctx,
-	f: BoxedResultFuture,
-) -> BoxedResultFuture {
+fn generate_actor_result_handler(id: ActorId, ctx: Ctx, f: BoxedResultFuture) -> BoxedResultFuture {
 	Box::pin(async move {
 		let result = task::spawn(f).await;
 
@@ -8,12 +8,12 @@ use super::{
 		messages::{BoxedMessage, Shutdown},
 		retry_strategy::{RetryStrategy, Strategy},
 	},
-	ActorFactory, ActorInstance, ActorStatus, LockedActorStatus, MessageQueue,
+	ActorFactory, ActorId, ActorInstance, ActorStatus, LockedActorStatus, MessageQueue,
 };
 use crate::prelude::*;
 
 pub struct ActorItem {
-	pub id: &'static str,
+	pub id: ActorId,
 	factory: ActorFactory,
 	status: LockedActorStatus,
 	instance: Option<ActorInstance>,
@@ -23,9 +23,9 @@ pub struct ActorItem {
 }
 
 impl ActorItem {
-	pub fn new(id: &'static str, factory: ActorFactory) -> Self {
+	pub fn new(id: impl Into<ActorId>, factory: ActorFactory) -> Self {
 		Self {
-			id,
+			id: id.into(),
 			factory,
 			status: LockedActorStatus::new(ActorStatus::Off),
 			instance: None,
@@ -36,7 +36,7 @@ impl ActorItem {
 	}
 
 	pub fn register_and_start(self, ctx: &mut Ctx) {
-		let id = self.id;
+		let id = self.id.clone();
 		ctx.register_actor(self);
 		ctx.start_actor(id);
 	}
@@ -95,7 +95,7 @@ impl ActorItem {
 					}
 				};
 
-				let id = self.id;
+				let id = self.id.clone();
 				let ctx = ctx.clone();
 				let status = self.status.clone();
 				self.status.set(ActorStatus::ArbiterRunning).await;
@@ -181,7 +181,7 @@ impl ActorItem {
 
 		let instance = self.instance.as_mut().unwrap();
 		instance
-			.start_actor_task(self.id, actor_type, ctx.clone())
+			.start_actor_task(self.id.clone(), actor_type, ctx.clone())
 			.await;
 
 		self.message_queue.send(&instance.event_channel);
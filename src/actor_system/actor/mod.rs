@@ -5,7 +5,7 @@ mod message_queue;
 mod registered_actors;
 mod status;
 
-use std::{pin::Pin, sync::Arc};
+use std::{borrow::Cow, pin::Pin, sync::Arc};
 
 pub use actor_type::ActorType;
 use async_trait::async_trait;
@@ -24,7 +24,10 @@ pub type BoxedContinousActor = Box<dyn ContinousActor + Send + Sync>;
 
 pub type BoxedResultFuture = Pin<Box<dyn Future<Output = Result<()>> + Send + Sync>>;
 
-pub type ActorFactory = &'static (dyn Fn() -> Actor + Send + Sync);
+pub type ActorFactory = Box<dyn Fn() -> Actor + Send + Sync>;
+
+// mostly static names, but actors created per server need owned ones
+pub type ActorId = Cow<'static, str>;
 
 #[async_trait]
 pub trait EventfulActor {
@@ -1,6 +1,10 @@
 use std::{any::Any, sync::Arc};
 
-use super::{actor::ActorItem, messages::SystemMessage, Sender};
+use super::{
+	actor::{ActorId, ActorItem},
+	messages::SystemMessage,
+	Sender,
+};
 use crate::prelude::*;
 
 #[derive(Clone)]
@@ -15,42 +19,42 @@ impl From<Sender<Arc<SystemMessage>>> for Ctx {
 }
 
 impl Ctx {
-	pub fn send_to<T: Any + Send + Sync + 'static>(&self, id: &'static str, msg: T) {
+	pub fn send_to<T: Any + Send + Sync + 'static>(&self, id: impl Into<ActorId>, msg: T) {
 		let _ = self
 			.internal_sx
-			.send(Arc::new(SystemMessage::SendMsg(id, Box::new(msg))));
+			.send(Arc::new(SystemMessage::SendMsg(id.into(), Box::new(msg))));
 	}
 	pub fn shutdown(&self) {
 		let _ = self.internal_sx.send(Arc::new(SystemMessage::Shutdown));
 	}
 	#[allow(dead_code)]
-	pub fn stop_actor(&self, id: &'static str) {
+	pub fn stop_actor(&self, id: impl Into<ActorId>) {
 		let _ = self
 			.internal_sx
-			.send(Arc::new(SystemMessage::StopActor(id)));
+			.send(Arc::new(SystemMessage::StopActor(id.into())));
 	}
 	#[allow(dead_code)]
-	pub fn restart_actor(&self, id: &'static str) {
+	pub fn restart_actor(&self, id: impl Into<ActorId>) {
 		let _ = self
 			.internal_sx
-			.send(Arc::new(SystemMessage::RestartActor(id)));
+			.send(Arc::new(SystemMessage::RestartActor(id.into())));
 	}
 	pub fn register_actor(&mut self, actor_item: ActorItem) {
 		let _ = self
 			.internal_sx
 			.send(Arc::new(SystemMessage::RegisterActor(actor_item)));
 	}
-	pub fn start_actor(&self, id: &'static str) {
+	pub fn start_actor(&self, id: impl Into<ActorId>) {
 		let _ = self
 			.internal_sx
-			.send(Arc::new(SystemMessage::StartActor(id)));
+			.send(Arc::new(SystemMessage::StartActor(id.into())));
 	}
-	pub fn actor_panicked(&self, id: &'static str) {
+	pub fn actor_panicked(&self, id: ActorId) {
 		let _ = self
 			.internal_sx
 			.send(Arc::new(SystemMessage::ActorTaskFinished(id, None)));
 	}
-	pub fn actor_returned(&self, id: &'static str, result: Result<()>) {
+	pub fn actor_returned(&self, id: ActorId, result: Result<()>) {
 		let _ = self
 			.internal_sx
 			.send(Arc::new(SystemMessage::ActorTaskFinished(id, Some(result))));
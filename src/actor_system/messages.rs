@@ -1,6 +1,6 @@
 use std::{any::Any, fmt::Debug};
 
-use super::actor::ActorItem;
+use super::actor::{ActorId, ActorItem};
 use crate::prelude::*;
 
 pub type BoxedMessage = Box<dyn Any + Send + Sync + 'static>;
@@ -10,11 +10,11 @@ impl<T> Message for T where T: Any + Send + Sync + Debug {}
 
 pub enum SystemMessage {
 	RegisterActor(ActorItem),
-	StopActor(&'static str),
-	StartActor(&'static str),
-	SendMsg(&'static str, BoxedMessage),
-	RestartActor(&'static str),
-	ActorTaskFinished(&'static str, Option<Result<()>>),
+	StopActor(ActorId),
+	StartActor(ActorId),
+	SendMsg(ActorId, BoxedMessage),
+	RestartActor(ActorId),
+	ActorTaskFinished(ActorId, Option<Result<()>>),
 	Shutdown,
 	// Broadcast(BoxedMessage),
 }
@@ -5,7 +5,7 @@ use tokio::sync::RwLock;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 pub use super::{
-	actor::{Actor, ActorItem, BoxedResultFuture, ContinousActor, EventfulActor},
+	actor::{Actor, ActorId, ActorItem, BoxedResultFuture, ContinousActor, EventfulActor},
 	context::Ctx,
 	messages::{BoxedMessage, Shutdown},
 	retry_strategy::PinnedClosure,
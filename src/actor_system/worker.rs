@@ -3,7 +3,7 @@ use std::{collections::HashMap, sync::Arc};
 use tokio::task;
 
 use super::{
-	actor::ActorItem,
+	actor::{ActorId, ActorItem},
 	context::Ctx,
 	messages::{BoxedMessage, SystemMessage},
 	Receiver, Sender, LOGGING_MODULE,
@@ -11,7 +11,7 @@ use super::{
 use crate::prelude::*;
 
 pub struct RegisteredActors {
-	items: HashMap<&'static str, ActorItem>,
+	items: HashMap<ActorId, ActorItem>,
 	ctx: Ctx,
 }
 
@@ -24,28 +24,28 @@ impl RegisteredActors {
 	}
 
 	pub fn register(&mut self, item: ActorItem) {
-		self.items.insert(item.id, item);
+		self.items.insert(item.id.clone(), item);
 	}
 
-	pub async fn stop(&mut self, id: &'static str) {
+	pub async fn stop(&mut self, id: &str) {
 		if let Some(item) = self.items.get_mut(id) {
 			item.stop().await;
 		}
 	}
 
-	pub async fn restart(&mut self, id: &'static str) {
+	pub async fn restart(&mut self, id: &str) {
 		if let Some(item) = self.items.get_mut(id) {
 			item.restart().await;
 		}
 	}
 
-	pub async fn stop_and_cache_messages(&mut self, id: &'static str) {
+	pub async fn stop_and_cache_messages(&mut self, id: &str) {
 		if let Some(item) = self.items.get_mut(id) {
 			item.stop_and_cache_messages().await;
 		}
 	}
 
-	pub async fn start(&mut self, id: &'static str) -> Result<()> {
+	pub async fn start(&mut self, id: &str) -> Result<()> {
 		if let Some(item) = self.items.get_mut(id) {
 			item.start(&self.ctx).await
 		} else {
@@ -53,13 +53,13 @@ impl RegisteredActors {
 		}
 	}
 
-	pub fn send(&mut self, id: &'static str, msg: BoxedMessage) {
+	pub fn send(&mut self, id: &str, msg: BoxedMessage) {
 		if let Some(item) = self.items.get_mut(id) {
 			item.send(msg);
 		}
 	}
 
-	pub async fn actor_task_finished(&mut self, id: &'static str, result: Option<Result<()>>) {
+	pub async fn actor_task_finished(&mut self, id: &str, result: Option<Result<()>>) {
 		if let Some(item) = self.items.get_mut(id) {
 			item.actor_task_finished(&self.ctx, result).await;
 		}
@@ -106,23 +106,23 @@ impl Worker {
 						self.actors.register(item);
 					}
 					SystemMessage::StartActor(id) => {
-						if let Err(e) = self.actors.start(id).await {
+						if let Err(e) = self.actors.start(&id).await {
 							error!("Failed to start actor {}.\n{:#?}", id, e);
 						}
 					}
 					SystemMessage::StopActor(id) => {
-						self.actors.stop(id).await;
+						self.actors.stop(&id).await;
 					}
 					SystemMessage::SendMsg(id, m) => {
-						self.actors.send(id, m);
+						self.actors.send(&id, m);
 					}
 					SystemMessage::ActorTaskFinished(id, result) => {
-						self.actors.stop_and_cache_messages(id).await;
+						self.actors.stop_and_cache_messages(&id).await;
 
-						self.actors.actor_task_finished(id, result).await;
+						self.actors.actor_task_finished(&id, result).await;
 					}
 					SystemMessage::RestartActor(id) => {
-						self.actors.restart(id).await;
+						self.actors.restart(&id).await;
 					}
 					SystemMessage::Shutdown => {
 						self.actors.shutdown().await;
@@ -0,0 +1,140 @@
+use std::{
+	fs, io,
+	os::unix::fs::{FileTypeExt, MetadataExt, PermissionsExt},
+	path::Path,
+	time::Duration,
+};
+
+use anyhow::Result;
+use tokio::{
+	io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+	net::{UnixListener, UnixStream},
+	sync::mpsc,
+	task, time,
+};
+use tokio_stream::StreamExt;
+
+use crate::{actor_system::prelude::*, models::ControlRequest, prelude::*, VARIABLES};
+
+static LOGGING_MODULE: &str = "ControlActor";
+
+// PulseAudio doesn't answer changes that change nothing, e.g. raising a maxed out volume
+const REPLY_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub struct ControlActor {}
+
+impl ControlActor {
+	pub fn factory() -> Actor {
+		Actor::Continous(Box::new(Self {}))
+	}
+
+	pub fn item() -> ActorItem {
+		ActorItem::new("control", Box::new(Self::factory))
+			.on_panic(|_| -> PinnedClosure { Box::pin(async { true }) })
+			.on_error(|_| -> PinnedClosure { Box::pin(async { true }) })
+	}
+}
+
+#[async_trait]
+impl ContinousActor for ControlActor {
+	async fn start(&mut self, _ctx: Ctx) {}
+	async fn stop(&mut self) {}
+
+	fn run(&mut self, ctx: Ctx, events_rx: LockedReceiver) -> BoxedResultFuture {
+		Box::pin(start(events_rx, ctx))
+	}
+}
+
+pub async fn start(rx: LockedReceiver, ctx: Ctx) -> Result<()> {
+	let mut rx = rx.write().await;
+
+	let path = (*VARIABLES).get().control_socket.clone();
+	let server = match path.as_deref().map(bind) {
+		Some(Ok(listener)) => Some(task::spawn(serve(listener, ctx))),
+		Some(Err(err)) => {
+			warn!("Control socket not available: {}", err);
+			None
+		}
+		None => None,
+	};
+
+	while let Some(ev) = rx.next().await {
+		if ev.is::<Shutdown>() {
+			break;
+		}
+	}
+
+	if let Some(server) = server {
+		server.abort();
+		if let Some(path) = path {
+			let _ = fs::remove_file(path);
+		}
+	}
+
+	Ok(())
+}
+
+// a socket left over by an instance that didn't exit cleanly is replaced, but
+// only if it's one of ours
+fn bind(path: &Path) -> io::Result<UnixListener> {
+	if let Ok(metadata) = fs::symlink_metadata(path) {
+		if !metadata.file_type().is_socket() || metadata.uid() != unsafe { libc::getuid() } {
+			return Err(io::Error::new(
+				io::ErrorKind::AlreadyExists,
+				format!("{} isn't a socket of this user", path.display()),
+			));
+		}
+		if std::os::unix::net::UnixStream::connect(path).is_ok() {
+			return Err(io::Error::new(
+				io::ErrorKind::AddrInUse,
+				format!("another rsmixer listens on {}", path.display()),
+			));
+		}
+		fs::remove_file(path)?;
+	}
+
+	let listener = UnixListener::bind(path)?;
+	fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+
+	Ok(listener)
+}
+
+async fn serve(listener: UnixListener, ctx: Ctx) {
+	loop {
+		match listener.accept().await {
+			Ok((stream, _)) => {
+				task::spawn(connection(stream, ctx.clone()));
+			}
+			Err(err) => {
+				warn!("{:?}", err);
+			}
+		}
+	}
+}
+
+// one request per line, every one is answered with a line of JSON
+async fn connection(stream: UnixStream, ctx: Ctx) -> Result<()> {
+	let (read, mut write) = stream.into_split();
+	let mut lines = BufReader::new(read).lines();
+
+	while let Some(line) = lines.next_line().await? {
+		let reply = request(&ctx, line).await;
+		write.write_all(format!("{reply}\n").as_bytes()).await?;
+	}
+
+	Ok(())
+}
+
+// falls back to the current state when the change isn't confirmed in time
+async fn request(ctx: &Ctx, request: String) -> String {
+	for request in [request, "state".to_string()] {
+		let (reply, mut reply_rx) = mpsc::unbounded_channel();
+		ctx.send_to("event_loop", ControlRequest { request, reply });
+
+		if let Ok(Some(reply)) = time::timeout(REPLY_TIMEOUT, reply_rx.recv()).await {
+			return reply;
+		}
+	}
+
+	r#"{"ok":false,"error":"no reply from rsmixer"}"#.to_string()
+}
@@ -1,30 +1,40 @@
-use std::{io::Stdout, pin::Pin};
+use std::{io::Stdout, pin::Pin, time::Duration};
 
 use anyhow::Result;
 use futures::Future;
+use tokio::{
+	task::{self, JoinHandle},
+	time,
+};
 
 use crate::{
 	action_handlers::*,
 	actor_system::prelude::*,
+	actors::pulse_actor_id,
 	models::{
-		EntryUpdate, PAStatus, PulseAudioAction, RSState, ResizeScreen, UserAction, UserInput,
+		ControlRequest, EntryUpdate, MeterTick, PAStatus, PulseAudioAction, RSState, ResizeScreen,
+		UserAction, UserInput,
 	},
-	ui, STYLES,
+	ui, STYLES, VARIABLES,
 };
 
 #[derive(Default)]
 pub struct EventLoopActor {
 	stdout: Option<Stdout>,
 	state: RSState,
+	pending_replies: Vec<control::PendingReply>,
+	meter_ticks: Option<JoinHandle<()>>,
 }
 
+const METER_TICK: Duration = Duration::from_millis(50);
+
 impl EventLoopActor {
 	pub fn factory() -> Actor {
 		Actor::Eventful(Box::new(Self::default()))
 	}
 
 	pub fn item() -> ActorItem {
-		ActorItem::new("event_loop", &Self::factory)
+		ActorItem::new("event_loop", Box::new(Self::factory))
 			.on_panic(|_| -> PinnedClosure { Box::pin(async { true }) })
 			.on_error(|_| -> PinnedClosure { Box::pin(async { true }) })
 	}
@@ -38,10 +48,28 @@ impl EventfulActor for EventLoopActor {
 		self.state.ui.buffer.set_styles((*STYLES).get().clone());
 		self.state.redraw.resize = true;
 
-		ctx.send_to("pulseaudio", PulseAudioAction::RequestPulseAudioState);
+		let tick_ctx = ctx.clone();
+		self.meter_ticks = Some(task::spawn(async move {
+			let mut interval = time::interval(METER_TICK);
+			loop {
+				interval.tick().await;
+				tick_ctx.send_to("event_loop", MeterTick {});
+			}
+		}));
+
+		for server in 0..(*VARIABLES).get().pa_servers.len() {
+			ctx.send_to(
+				pulse_actor_id(server),
+				PulseAudioAction::RequestPulseAudioState,
+			);
+		}
 	}
 
 	async fn stop(&mut self) {
+		if let Some(ticks) = self.meter_ticks.take() {
+			ticks.abort();
+		}
+
 		ui::clean_terminal().unwrap();
 	}
 
@@ -55,6 +83,7 @@ impl EventfulActor for EventLoopActor {
 				let msg = msg.downcast_ref::<EntryUpdate>().unwrap();
 
 				pulseaudio_info::handle(msg, &mut self.state);
+				control::on_update(msg, &self.state, &mut self.pending_replies);
 			} else if msg.is::<PAStatus>() {
 				let msg = msg.downcast_ref::<PAStatus>().unwrap();
 
@@ -67,8 +96,14 @@ impl EventfulActor for EventLoopActor {
 				let msg = msg.downcast_ref::<UserAction>().unwrap();
 
 				user_action::handle(msg, &mut self.state, &ctx);
+			} else if msg.is::<ControlRequest>() {
+				let msg = msg.downcast_ref::<ControlRequest>().unwrap();
+
+				control::handle(msg, &mut self.state, &ctx, &mut self.pending_replies);
 			} else if msg.is::<ResizeScreen>() {
 				self.state.redraw.resize = true;
+			} else if msg.is::<MeterTick>() {
+				self.state.advance_meters();
 			}
 
 			if self.state.redraw.anything() {
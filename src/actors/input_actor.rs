@@ -15,7 +15,7 @@ impl InputActor {
 	}
 
 	pub fn item() -> ActorItem {
-		ActorItem::new("input", &Self::factory)
+		ActorItem::new("input", Box::new(Self::factory))
 			.on_panic(|_| -> PinnedClosure { Box::pin(async { true }) })
 			.on_error(|_| -> PinnedClosure { Box::pin(async { true }) })
 	}
@@ -1,7 +1,9 @@
+mod control_actor;
 mod event_loop_actor;
 mod input_actor;
 mod pa_actor;
 
+pub use control_actor::ControlActor;
 pub use event_loop_actor::EventLoopActor;
 pub use input_actor::InputActor;
-pub use pa_actor::PulseActor;
+pub use pa_actor::{pulse_actor_id, PulseActor};
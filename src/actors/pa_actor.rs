@@ -1,11 +1,9 @@
-use std::time::Duration;
-
 use anyhow::Result;
-use tokio::{sync::mpsc, task};
-use tokio_stream::{
-	wrappers::{IntervalStream, UnboundedReceiverStream},
-	StreamExt,
+use tokio::{
+	sync::mpsc,
+	task::{self, JoinHandle},
 };
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 
 use crate::{
 	actor_system::prelude::*,
@@ -14,16 +12,32 @@ use crate::{
 	VARIABLES,
 };
 
-pub struct PulseActor {}
+// one actor per configured server, the first one keeps the plain name; ids of
+// servers that aren't configured belong to no actor, so messages to them are dropped
+pub fn pulse_actor_id(server: usize) -> ActorId {
+	debug_assert!(server < (*VARIABLES).get().pa_servers.len());
+
+	match server {
+		0 => ActorId::Borrowed("pulseaudio"),
+		_ => ActorId::Owned(format!("pulseaudio-{server}")),
+	}
+}
+
+pub struct PulseActor {
+	server: usize,
+}
 
 impl PulseActor {
-	pub fn factory() -> Actor {
-		Actor::Continous(Box::new(Self {}))
+	pub fn factory(server: usize) -> Actor {
+		Actor::Continous(Box::new(Self { server }))
 	}
-	pub fn item() -> ActorItem {
-		ActorItem::new("pulseaudio", &Self::factory)
-			.on_panic(|_| -> PinnedClosure { Box::pin(async { true }) })
-			.on_error(|_| -> PinnedClosure { Box::pin(async { true }) })
+	pub fn item(server: usize) -> ActorItem {
+		ActorItem::new(
+			pulse_actor_id(server),
+			Box::new(move || Self::factory(server)),
+		)
+		.on_panic(|_| -> PinnedClosure { Box::pin(async { true }) })
+		.on_error(|_| -> PinnedClosure { Box::pin(async { true }) })
 	}
 }
 
@@ -32,13 +46,11 @@ impl ContinousActor for PulseActor {
 	async fn start(&mut self, _ctx: Ctx) {}
 	async fn stop(&mut self) {}
 	fn run(&mut self, ctx: Ctx, events_rx: LockedReceiver) -> BoxedResultFuture {
-		Box::pin(start_async(events_rx, ctx))
+		Box::pin(start_async(events_rx, ctx, self.server))
 	}
 }
 
-async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
-	let mut interval = IntervalStream::new(tokio::time::interval(Duration::from_millis(50)));
-
+async fn start_async(external_rx: LockedReceiver, ctx: Ctx, server_id: usize) -> Result<()> {
 	let send = |ch: &cb_channel::Sender<PAInternal>, msg: PAInternal| -> Result<()> {
 		match ch.send(msg) {
 			Ok(()) => Ok(()),
@@ -46,6 +58,7 @@ async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
 		}
 	};
 	let retry_time = (*VARIABLES).get().pa_retry_time;
+	let server = (*VARIABLES).get().pa_servers[server_id].clone();
 	let mut external_rx = external_rx.write().await;
 
 	loop {
@@ -53,9 +66,18 @@ async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
 		let (internal_actions_sx, internal_actions_rx) = mpsc::unbounded_channel::<EntryUpdate>();
 		let (internal_sx, internal_rx) = cb_channel::unbounded();
 		let (pa_finished_sx, pa_finished_rx) = mpsc::unbounded_channel();
+		let (monitors_sx, monitors_rx) = cb_channel::unbounded();
 
+		// meters have their own connection, see `pa::start_monitors`
+		let address = server.clone();
+		let monitor_actions_sx = internal_actions_sx.clone();
+		let sync_monitors = task::spawn_blocking(move || {
+			pa::start_monitors(monitors_rx, monitor_actions_sx, address);
+		});
+
+		let address = server.clone();
 		let sync_pa = task::spawn_blocking(move || {
-			let res = pa::start(internal_rx, info_sx, internal_actions_sx);
+			let res = pa::start(internal_rx, info_sx, internal_actions_sx, address);
 			let _ = pa_finished_sx.send(res);
 		});
 
@@ -63,18 +85,21 @@ async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
 		let mut internal_actions_rx = UnboundedReceiverStream::new(internal_actions_rx);
 		let mut info_rx = UnboundedReceiverStream::new(info_rx);
 
-		ctx.send_to("event_loop", PAStatus::ConnectToPulseAudio);
+		ctx.send_to("event_loop", PAStatus::ConnectToPulseAudio(server_id));
 
 		loop {
 			let res = external_rx.next();
 			let finished = pa_finished_rx.next();
 			let actions = internal_actions_rx.next();
 			let info = info_rx.next();
-			let timeout = interval.next();
 
 			tokio::select! {
 				r = res => {
 					if let Some(cmd) = r {
+						if let Some(PulseAudioAction::CreateMonitors(targets)) = cmd.downcast_ref::<PulseAudioAction>() {
+							let _ = monitors_sx.send(targets.clone());
+							continue;
+						}
 						if cmd.is::<PulseAudioAction>() {
 							if let Some(cmd) = cmd.downcast_ref::<PulseAudioAction>() {
 								internal_sx.send(PAInternal::Command(Box::new(cmd.clone())))?;
@@ -84,6 +109,8 @@ async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
 						if cmd.downcast_ref::<Shutdown>().is_some() {
 							internal_sx.send(PAInternal::Command(Box::new(PulseAudioAction::Shutdown)))?;
 							sync_pa.await.unwrap();
+							drop(monitors_sx);
+							join_monitors(sync_monitors).await;
 							return Ok(());
 						}
 					}
@@ -92,7 +119,8 @@ async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
 					break;
 				}
 				i = actions => {
-					if let Some(action) = i {
+					if let Some(mut action) = i {
+						action.set_server(server_id, &server);
 						ctx.send_to("event_loop", action);
 					}
 				}
@@ -101,14 +129,13 @@ async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
 						send(&internal_sx, PAInternal::AskInfo(ident))?;
 					}
 				}
-				_ = timeout => {
-					send(&internal_sx, PAInternal::Tick)?;
-				}
 			};
 		}
-		ctx.send_to("event_loop", PAStatus::PulseAudioDisconnected);
+		drop(monitors_sx);
+		join_monitors(sync_monitors).await;
+		ctx.send_to("event_loop", PAStatus::PulseAudioDisconnected(server_id));
 		for i in 0..retry_time {
-			ctx.send_to("event_loop", PAStatus::RetryIn(retry_time - i));
+			ctx.send_to("event_loop", PAStatus::RetryIn(server_id, retry_time - i));
 
 			let timeout_part = tokio::time::sleep(std::time::Duration::from_secs(1));
 			let event = external_rx.next();
@@ -125,3 +152,10 @@ async fn start_async(external_rx: LockedReceiver, ctx: Ctx) -> Result<()> {
 		}
 	}
 }
+
+// a panic on the meter thread only costs the meters, not the connection
+async fn join_monitors(handle: JoinHandle<()>) {
+	if let Err(err) = handle.await {
+		warn!("Meter thread failed: {:?}", err);
+	}
+}
@@ -10,20 +10,172 @@ pub struct CliOptions {
 	#[options(count, help = "verbosity. Once - info, twice - debug")]
 	verbose: usize,
 
+	#[options(
+		help = "PulseAudio server to connect to, e.g. tcp:192.168.1.10 (can be repeated, overrides config)"
+	)]
+	pub server: Vec<String>,
+
+	#[options(help = "show this text")]
+	help: bool,
+
+	#[options(command)]
+	pub command: Option<Command>,
+}
+
+// Commands run once and exit instead of starting the TUI. Targets are matched
+// by index, then by exact name, then as a regex against names.
+#[derive(Debug, Options)]
+pub enum Command {
+	#[options(help = "list sinks, sources, streams and cards")]
+	List(ListOptions),
+	#[options(help = "set volume, e.g. 50%, +5, -3dB (use -- before negative values)")]
+	SetVolume(SetVolumeOptions),
+	#[options(help = "mute matching sinks, sources or streams")]
+	Mute(TargetOptions),
+	#[options(help = "unmute matching sinks, sources or streams")]
+	Unmute(TargetOptions),
+	#[options(help = "toggle mute of matching sinks, sources or streams")]
+	Toggle(TargetOptions),
+	#[options(help = "move streams to another sink or source")]
+	Move(MoveOptions),
+	#[options(help = "change the active profile of a card")]
+	SetProfile(SetProfileOptions),
+	#[options(help = "print the whole mixer state as JSON or TOML")]
+	Dump(DumpOptions),
+	#[options(help = "print every change as a line of JSON until interrupted")]
+	Watch(WatchOptions),
+	#[options(help = "print a status bar line for the default sink on every change")]
+	Bar(BarOptions),
+}
+
+#[derive(Debug, Options)]
+pub struct ListOptions {
+	#[options(help = "show this text")]
+	help: bool,
+}
+
+#[derive(Debug, Options)]
+pub struct DumpOptions {
+	#[options(help = "show this text")]
+	help: bool,
+
+	#[options(no_short, help = "print JSON (default)")]
+	pub json: bool,
+
+	#[options(no_short, help = "print TOML")]
+	pub toml: bool,
+}
+
+#[derive(Debug, Options)]
+pub struct WatchOptions {
+	#[options(help = "show this text")]
+	help: bool,
+
+	#[options(no_short, help = "also print meter levels")]
+	pub peaks: bool,
+
+	#[options(
+		no_short,
+		meta = "MS",
+		help = "print meter levels at most this often (default 200)"
+	)]
+	pub peak_interval: Option<u64>,
+}
+
+#[derive(Debug, Options)]
+pub struct BarOptions {
 	#[options(help = "show this text")]
 	help: bool,
+
+	#[options(no_short, help = "follow the default source instead")]
+	pub source: bool,
+
+	#[options(
+		no_short,
+		meta = "FORMAT",
+		help = "{icon}, {volume}, {name} and {device} are filled in (default \"{icon} {volume}%\")"
+	)]
+	pub format: Option<String>,
+
+	#[options(no_short, meta = "FORMAT", help = "format used when muted")]
+	pub format_muted: Option<String>,
+
+	#[options(
+		no_short,
+		meta = "ICONS",
+		help = "comma separated icons, from quiet to loud"
+	)]
+	pub icons: Option<String>,
+
+	#[options(no_short, meta = "ICON", help = "icon shown when muted")]
+	pub muted_icon: Option<String>,
+
+	#[options(no_short, help = "print JSON for waybar")]
+	pub waybar: bool,
+
+	#[options(
+		no_short,
+		meta = "PERCENT",
+		help = "waybar class is high above this volume (default 100)"
+	)]
+	pub high: Option<u16>,
+}
+
+#[derive(Debug, Options)]
+pub struct TargetOptions {
+	#[options(help = "show this text")]
+	help: bool,
+
+	#[options(free, required, help = "index, name or regex")]
+	pub target: String,
+}
+
+#[derive(Debug, Options)]
+pub struct SetVolumeOptions {
+	#[options(help = "show this text")]
+	help: bool,
+
+	#[options(free, required, help = "index, name or regex")]
+	pub target: String,
+
+	#[options(free, required, help = "volume, e.g. 50%, +5, -3dB")]
+	pub value: String,
+}
+
+#[derive(Debug, Options)]
+pub struct MoveOptions {
+	#[options(help = "show this text")]
+	help: bool,
+
+	#[options(free, required, help = "stream index, name or regex")]
+	pub stream: String,
+
+	#[options(free, required, help = "sink or source index, name or regex")]
+	pub to: String,
+}
+
+#[derive(Debug, Options)]
+pub struct SetProfileOptions {
+	#[options(help = "show this text")]
+	help: bool,
+
+	#[options(free, required, help = "card index, name or regex")]
+	pub card: String,
+
+	#[options(free, required, help = "profile name or description")]
+	pub profile: String,
 }
 
 impl CliOptions {
-	pub fn check() -> Result<()> {
+	pub fn check() -> Result<Self> {
 		let opts = CliOptions::parse_args_default_or_exit();
 
 		if opts.help {
 			println!("{}", CliOptions::usage());
-			return Ok(());
+			return Ok(opts);
 		}
 
-		if let Some(file) = opts.log_file {
+		if let Some(file) = &opts.log_file {
 			let lvl = match opts.verbose {
 				2 => LevelFilter::Debug,
 				1 => LevelFilter::Info,
@@ -32,6 +184,6 @@ impl CliOptions {
 			simple_logging::log_to_file(file, lvl).unwrap();
 		}
 
-		Ok(())
+		Ok(opts)
 	}
 }
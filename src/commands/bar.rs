@@ -0,0 +1,184 @@
+use std::{collections::HashMap, io::Write};
+
+use serde::Serialize;
+
+use super::Subscription;
+use crate::{
+	cli_options::BarOptions,
+	entry::{Entry, EntryIdentifier, EntryType, PlayEntry},
+	models::{EntryUpdate, ServerInfo},
+	prelude::*,
+	util::volume_to_percent,
+};
+
+const DEFAULT_FORMAT: &str = "{icon} {volume}%";
+const DEFAULT_FORMAT_MUTED: &str = "{icon} muted";
+const DEFAULT_HIGH: u16 = 100;
+
+// what waybar expects from a custom module with `"return-type": "json"`
+#[derive(Serialize)]
+struct Waybar<'a> {
+	text: &'a str,
+	tooltip: &'a str,
+	class: &'a str,
+	percentage: u16,
+}
+
+struct Bar<'a> {
+	opts: &'a BarOptions,
+	entry_type: EntryType,
+	devices: HashMap<EntryIdentifier, Entry>,
+	server_info: Option<ServerInfo>,
+}
+
+pub async fn bar(server: Option<String>, opts: &BarOptions) -> Result<()> {
+	let mut subscription = Subscription::connect(server.as_deref())?;
+	let mut bar = Bar::new(opts);
+	let mut last_line = None;
+
+	loop {
+		bar.update(subscription.next().await?);
+
+		let line = match bar.line()? {
+			Some(line) => line,
+			None => continue,
+		};
+		if last_line.as_ref() == Some(&line) {
+			continue;
+		}
+
+		// nobody reads the output anymore
+		if writeln!(std::io::stdout(), "{line}").is_err() {
+			return Ok(());
+		}
+		last_line = Some(line);
+	}
+}
+
+impl<'a> Bar<'a> {
+	fn new(opts: &'a BarOptions) -> Self {
+		Self {
+			opts,
+			entry_type: if opts.source {
+				EntryType::Source
+			} else {
+				EntryType::Sink
+			},
+			devices: HashMap::new(),
+			server_info: None,
+		}
+	}
+
+	fn update(&mut self, update: EntryUpdate) {
+		match update {
+			EntryUpdate::EntryUpdate(ident, entry) if ident.entry_type == self.entry_type => {
+				self.devices.insert(ident, *entry);
+			}
+			EntryUpdate::EntryRemoved(ident) => {
+				self.devices.remove(&ident);
+			}
+			EntryUpdate::ServerInfo(info) => {
+				self.server_info = Some(info);
+			}
+			_ => {}
+		}
+	}
+
+	fn default_device(&self) -> Option<(&Entry, &PlayEntry)> {
+		let info = self.server_info.as_ref()?;
+
+		self.devices.values().find_map(|entry| {
+			let play = entry.entry_kind.play_entry()?;
+
+			if info.is_default(entry.entry_type, &play.device_name) {
+				Some((entry, play))
+			} else {
+				None
+			}
+		})
+	}
+
+	// `None` until the server info arrives
+	fn line(&self) -> Result<Option<String>> {
+		if self.server_info.is_none() {
+			return Ok(None);
+		}
+
+		let (text, tooltip, class, percentage) = match self.default_device() {
+			Some((entry, play)) => {
+				let volume = volume_to_percent(play.volume);
+
+				let class = if play.mute {
+					"muted"
+				} else if volume > self.opts.high.unwrap_or(DEFAULT_HIGH) {
+					"high"
+				} else {
+					"normal"
+				};
+
+				(
+					self.text(entry, play, volume),
+					entry.name.as_str(),
+					class,
+					volume,
+				)
+			}
+			None => (String::new(), "", "unavailable", 0),
+		};
+
+		if !self.opts.waybar {
+			return Ok(Some(text));
+		}
+
+		Ok(Some(serde_json::to_string(&Waybar {
+			text: &text,
+			tooltip,
+			class,
+			percentage,
+		})?))
+	}
+
+	fn text(&self, entry: &Entry, play: &PlayEntry, volume: u16) -> String {
+		let (format, icon) = if play.mute {
+			(
+				self.opts
+					.format_muted
+					.as_deref()
+					.unwrap_or(DEFAULT_FORMAT_MUTED),
+				self.muted_icon(),
+			)
+		} else {
+			(
+				self.opts.format.as_deref().unwrap_or(DEFAULT_FORMAT),
+				self.icon(volume),
+			)
+		};
+
+		format
+			.replace("{icon}", icon)
+			.replace("{volume}", &volume.to_string())
+			.replace("{name}", &entry.name)
+			.replace("{device}", play.device_name.as_deref().unwrap_or_default())
+	}
+
+	// icons split the range up to 100% evenly, louder gets the last one
+	fn icon(&self, volume: u16) -> &str {
+		let icons: Vec<&str> = match &self.opts.icons {
+			Some(icons) => icons.split(',').map(str::trim).collect(),
+			None if self.opts.source => vec!["🎤"],
+			None => vec!["🔈", "🔉", "🔊"],
+		};
+
+		let i = (volume.min(100) as usize * icons.len() / 101).min(icons.len() - 1);
+
+		icons[i]
+	}
+
+	fn muted_icon(&self) -> &str {
+		match &self.opts.muted_icon {
+			Some(icon) => icon,
+			None if self.opts.source => "🎤",
+			None => "🔇",
+		}
+	}
+}
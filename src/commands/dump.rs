@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+use crate::{entry::Entry, models::ServerInfo, pa::Snapshot, prelude::*};
+
+#[derive(Serialize)]
+struct Dump<'a> {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	server: Option<&'a ServerInfo>,
+	entries: Vec<&'a Entry>,
+}
+
+pub fn dump(snapshot: &Snapshot, toml: bool) -> Result<()> {
+	let mut entries: Vec<&Entry> = snapshot.entries.values().collect();
+	entries.sort_by_key(|e| (e.entry_type, e.index));
+
+	let dump = Dump {
+		server: snapshot.server_info.as_ref(),
+		entries,
+	};
+
+	let text = if toml {
+		toml::to_string_pretty(&dump)?
+	} else {
+		serde_json::to_string_pretty(&dump)?
+	};
+	println!("{}", text.trim_end());
+
+	Ok(())
+}
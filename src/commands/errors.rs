@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CommandError {
+	#[error("'{0}' is not a valid regex")]
+	InvalidPattern(String),
+	#[error("'{0}' is not a valid volume")]
+	InvalidVolume(String),
+	#[error("nothing matches '{0}'")]
+	NoMatch(String),
+	#[error("'{0}' matches more than one entry")]
+	Ambiguous(String),
+	#[error("card '{0}' has no profile '{1}'")]
+	NoProfile(String, String),
+}
+
+impl CommandError {
+	// 1 is left for connection and PulseAudio errors
+	pub fn exit_code(&self) -> i32 {
+		match self {
+			Self::InvalidPattern(_) | Self::InvalidVolume(_) => 2,
+			Self::NoMatch(_) | Self::Ambiguous(_) | Self::NoProfile(_, _) => 3,
+		}
+	}
+}
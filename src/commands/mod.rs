@@ -0,0 +1,263 @@
+mod bar;
+mod dump;
+mod errors;
+mod subscription;
+pub mod targets;
+mod watch;
+
+use std::future::Future;
+
+pub use errors::CommandError;
+use subscription::Subscription;
+use tokio::runtime;
+
+use crate::{
+	cli_options::Command,
+	entry::{Entry, EntryKind, EntryType},
+	models::PulseAudioAction,
+	pa::{BlockingConnection, Snapshot},
+	prelude::*,
+	util::{parse_volume_input, volume_to_percent},
+	VARIABLES,
+};
+
+static LOGGING_MODULE: &str = "Commands";
+
+pub const PLAY_TYPES: [EntryType; 4] = [
+	EntryType::Sink,
+	EntryType::Source,
+	EntryType::SinkInput,
+	EntryType::SourceOutput,
+];
+
+// runs a command against the first configured server, returns the exit code
+pub fn run(command: Command) -> i32 {
+	match execute(command) {
+		Ok(()) => 0,
+		Err(err) => {
+			eprintln!("rsmixer: {err:#}");
+
+			err.downcast_ref::<CommandError>()
+				.map_or(1, CommandError::exit_code)
+		}
+	}
+}
+
+fn execute(command: Command) -> Result<()> {
+	let server = (*VARIABLES).get().pa_servers.first().cloned().flatten();
+
+	debug!(
+		"Connecting to {}",
+		server.as_deref().unwrap_or("default server")
+	);
+
+	match command {
+		Command::List(_) => once(server, |_, snapshot| {
+			list(snapshot);
+			Ok(())
+		}),
+		Command::SetVolume(opts) => once(server, |connection, snapshot| {
+			set_volume(connection, snapshot, &opts.target, &opts.value)
+		}),
+		Command::Mute(opts) => once(server, |connection, snapshot| {
+			set_mute(connection, snapshot, &opts.target, Some(true))
+		}),
+		Command::Unmute(opts) => once(server, |connection, snapshot| {
+			set_mute(connection, snapshot, &opts.target, Some(false))
+		}),
+		Command::Toggle(opts) => once(server, |connection, snapshot| {
+			set_mute(connection, snapshot, &opts.target, None)
+		}),
+		Command::Move(opts) => once(server, |connection, snapshot| {
+			move_streams(connection, snapshot, &opts.stream, &opts.to)
+		}),
+		Command::Dump(opts) => once(server, |_, snapshot| {
+			dump::dump(snapshot, opts.toml && !opts.json)
+		}),
+		Command::SetProfile(opts) => once(server, |connection, snapshot| {
+			set_profile(connection, snapshot, &opts.card, &opts.profile)
+		}),
+		// these keep their own connection open until interrupted
+		Command::Watch(opts) => block_on(watch::watch(server, &opts)),
+		Command::Bar(opts) => block_on(bar::bar(server, &opts)),
+	}
+}
+
+// connects and takes a snapshot of the current state for a command that runs once
+fn once(
+	server: Option<String>,
+	command: impl FnOnce(&BlockingConnection, &Snapshot) -> Result<()>,
+) -> Result<()> {
+	let connection = BlockingConnection::connect(server.as_deref())?;
+	let snapshot = connection.snapshot()?;
+
+	command(&connection, &snapshot)
+}
+
+fn block_on(future: impl Future<Output = Result<()>>) -> Result<()> {
+	runtime::Builder::new_current_thread()
+		.enable_time()
+		.build()?
+		.block_on(future)
+}
+
+pub fn type_name(entry_type: EntryType) -> &'static str {
+	match entry_type {
+		EntryType::Sink => "sink",
+		EntryType::Source => "source",
+		EntryType::SinkInput => "sink-input",
+		EntryType::SourceOutput => "source-output",
+		EntryType::Card => "card",
+		EntryType::Module => "module",
+		EntryType::Client => "client",
+	}
+}
+
+// tab separated: type, index, volume, state and name
+fn list(snapshot: &Snapshot) {
+	let mut entries: Vec<&Entry> = snapshot.entries.values().collect();
+	entries.sort_by_key(|e| (e.entry_type, e.index));
+
+	for entry in entries {
+		let (volume, state) = match &entry.entry_kind {
+			EntryKind::PlayEntry(play) => {
+				let mut state = Vec::new();
+				if play.mute {
+					state.push("muted");
+				}
+				if play.is_default {
+					state.push("default");
+				}
+				if state.is_empty() {
+					state.push("-");
+				}
+
+				(
+					format!("{}%", volume_to_percent(play.volume)),
+					state.join(","),
+				)
+			}
+			// the active profile takes the place of the state
+			EntryKind::CardEntry(card) => (
+				"-".to_string(),
+				card.selected_profile
+					.and_then(|i| card.profiles.get(i))
+					.map_or_else(|| "-".to_string(), |p| p.name.clone()),
+			),
+			_ => {
+				continue;
+			}
+		};
+
+		println!(
+			"{}\t{}\t{}\t{}\t{}",
+			type_name(entry.entry_type),
+			entry.index,
+			volume,
+			state,
+			entry.name
+		);
+	}
+}
+
+fn set_volume(
+	connection: &BlockingConnection,
+	snapshot: &Snapshot,
+	target: &str,
+	value: &str,
+) -> Result<()> {
+	for entry in targets::matching(snapshot, &PLAY_TYPES, target)? {
+		let play = match entry.entry_kind.play_entry() {
+			Some(play) => play,
+			None => continue,
+		};
+		let max = (*VARIABLES).get().max_volume(play.device_name.as_deref()) as i16;
+
+		let volume = parse_volume_input(value, play.volume.avg(), max)
+			.ok_or_else(|| CommandError::InvalidVolume(value.to_string()))?;
+
+		let mut volumes = play.volume;
+		for v in volumes.get_mut() {
+			*v = volume;
+		}
+
+		connection.run(PulseAudioAction::SetVolume(entry.entry_ident, volumes))?;
+	}
+
+	Ok(())
+}
+
+// `None` toggles
+fn set_mute(
+	connection: &BlockingConnection,
+	snapshot: &Snapshot,
+	target: &str,
+	mute: Option<bool>,
+) -> Result<()> {
+	for entry in targets::matching(snapshot, &PLAY_TYPES, target)? {
+		let play = match entry.entry_kind.play_entry() {
+			Some(play) => play,
+			None => continue,
+		};
+
+		connection.run(PulseAudioAction::MuteEntry(
+			entry.entry_ident,
+			mute.unwrap_or(!play.mute),
+		))?;
+	}
+
+	Ok(())
+}
+
+fn move_streams(
+	connection: &BlockingConnection,
+	snapshot: &Snapshot,
+	stream: &str,
+	to: &str,
+) -> Result<()> {
+	let streams = targets::matching(
+		snapshot,
+		&[EntryType::SinkInput, EntryType::SourceOutput],
+		stream,
+	)?;
+
+	for entry in streams {
+		let parent_type = match entry.entry_type {
+			EntryType::SinkInput => EntryType::Sink,
+			_ => EntryType::Source,
+		};
+		let parent = targets::unique(snapshot, &[parent_type], to)?;
+
+		connection.run(PulseAudioAction::MoveEntryToParent(
+			entry.entry_ident,
+			parent.entry_ident,
+		))?;
+	}
+
+	Ok(())
+}
+
+fn set_profile(
+	connection: &BlockingConnection,
+	snapshot: &Snapshot,
+	card: &str,
+	profile: &str,
+) -> Result<()> {
+	for entry in targets::matching(snapshot, &[EntryType::Card], card)? {
+		let card_entry = match entry.entry_kind.card_entry() {
+			Some(card) => card,
+			None => continue,
+		};
+
+		let name = card_entry
+			.profiles
+			.iter()
+			.find(|p| p.name == profile || p.description == profile)
+			.map(|p| p.name.clone())
+			.ok_or_else(|| CommandError::NoProfile(entry.name.clone(), profile.to_string()))?;
+
+		connection.run(PulseAudioAction::ChangeCardProfile(entry.entry_ident, name))?;
+	}
+
+	Ok(())
+}
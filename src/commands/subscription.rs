@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use tokio::{
+	sync::mpsc,
+	time::{self, Interval},
+};
+
+use crate::{
+	entry::EntryIdentifier,
+	models::EntryUpdate,
+	pa::{
+		common::{ActionsSender, PAError},
+		BlockingConnection,
+	},
+	prelude::*,
+};
+
+// Updates from the same subscription the TUI uses, for commands that keep
+// running. Fields are dropped in order, the connection goes before the
+// receivers its callbacks send to.
+pub struct Subscription {
+	connection: BlockingConnection,
+	info_sx: mpsc::UnboundedSender<EntryIdentifier>,
+	info_rx: mpsc::UnboundedReceiver<EntryIdentifier>,
+	actions_sx: ActionsSender,
+	actions_rx: mpsc::UnboundedReceiver<EntryUpdate>,
+	ready_tick: Interval,
+}
+
+impl Subscription {
+	pub fn connect(server: Option<&str>) -> Result<Self> {
+		let (info_sx, info_rx) = mpsc::unbounded_channel();
+		let (actions_sx, actions_rx) = mpsc::unbounded_channel();
+
+		let connection = BlockingConnection::connect(server)?;
+		connection.subscribe(info_sx.clone(), &actions_sx)?;
+
+		Ok(Self {
+			connection,
+			info_sx,
+			info_rx,
+			actions_sx,
+			actions_rx,
+			ready_tick: time::interval(Duration::from_secs(1)),
+		})
+	}
+
+	// for meters, which have their own connection
+	pub fn actions_sx(&self) -> ActionsSender {
+		self.actions_sx.clone()
+	}
+
+	// starts with the current state, entries are asked for on the way
+	pub async fn next(&mut self) -> Result<EntryUpdate> {
+		loop {
+			tokio::select! {
+				Some(ident) = self.info_rx.recv() => {
+					self.connection.request_info(ident, self.info_sx.clone(), &self.actions_sx);
+				}
+				Some(update) = self.actions_rx.recv() => {
+					return Ok(update);
+				}
+				_ = self.ready_tick.tick() => {
+					if !self.connection.is_ready() {
+						return Err(PAError::PulseAudioDisconnected.into());
+					}
+				}
+			}
+		}
+	}
+}
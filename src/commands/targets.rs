@@ -0,0 +1,188 @@
+use regex::Regex;
+
+use super::CommandError;
+use crate::{
+	entry::{Entry, EntryType},
+	pa::Snapshot,
+};
+
+pub fn matching<'a>(
+	snapshot: &'a Snapshot,
+	types: &[EntryType],
+	target: &str,
+) -> Result<Vec<&'a Entry>, CommandError> {
+	matching_entries(snapshot.entries.values(), types, target)
+}
+
+// A number is an index, anything else is first compared with the names of
+// entries and when nothing is called exactly that it's used as a regex.
+pub fn matching_entries<'a>(
+	entries: impl Iterator<Item = &'a Entry>,
+	types: &[EntryType],
+	target: &str,
+) -> Result<Vec<&'a Entry>, CommandError> {
+	let mut candidates: Vec<&Entry> = entries.filter(|e| types.contains(&e.entry_type)).collect();
+	candidates.sort_by_key(|e| (e.entry_type, e.index));
+
+	let found: Vec<&Entry> = if let Ok(index) = target.parse::<u32>() {
+		candidates
+			.into_iter()
+			.filter(|e| e.index == index)
+			.collect()
+	} else if candidates.iter().any(|e| names(e).contains(&target)) {
+		candidates
+			.into_iter()
+			.filter(|e| names(e).contains(&target))
+			.collect()
+	} else {
+		let regex =
+			Regex::new(target).map_err(|_| CommandError::InvalidPattern(target.to_string()))?;
+
+		candidates
+			.into_iter()
+			.filter(|e| names(e).iter().any(|n| regex.is_match(n)))
+			.collect()
+	};
+
+	if found.is_empty() {
+		return Err(CommandError::NoMatch(target.to_string()));
+	}
+
+	Ok(found)
+}
+
+pub fn unique<'a>(
+	snapshot: &'a Snapshot,
+	types: &[EntryType],
+	target: &str,
+) -> Result<&'a Entry, CommandError> {
+	let found = matching(snapshot, types, target)?;
+
+	if found.len() > 1 {
+		return Err(CommandError::Ambiguous(target.to_string()));
+	}
+
+	Ok(found[0])
+}
+
+fn names(entry: &Entry) -> Vec<&str> {
+	let mut names = vec![entry.name.as_str()];
+
+	if let Some(play) = entry.entry_kind.play_entry() {
+		names.extend(play.device_name.as_deref());
+		names.extend(play.media_name.as_deref());
+		names.extend(play.binary.as_deref());
+	}
+
+	names
+}
+
+#[cfg(test)]
+mod tests {
+	use pulse::volume::ChannelVolumes;
+
+	use super::*;
+
+	fn entry(entry_type: EntryType, index: u32, name: &str, device_name: Option<&str>) -> Entry {
+		Entry::new_play_entry(
+			entry_type,
+			index,
+			name.to_string(),
+			None,
+			false,
+			ChannelVolumes::default(),
+			None,
+			None,
+			false,
+			device_name.map(str::to_string),
+		)
+	}
+
+	fn entries() -> Vec<Entry> {
+		vec![
+			entry(EntryType::Sink, 1, "2", Some("alsa_output.speakers")),
+			entry(
+				EntryType::Sink,
+				2,
+				"Headphones",
+				Some("bluez_sink.headphones"),
+			),
+			entry(EntryType::SinkInput, 3, "firefox", None),
+			entry(EntryType::SinkInput, 4, "firefox-nightly", None),
+			entry(EntryType::Source, 5, "firefox", None),
+		]
+	}
+
+	fn indexes(entries: &[Entry], types: &[EntryType], target: &str) -> Vec<u32> {
+		matching_entries(entries.iter(), types, target)
+			.unwrap()
+			.iter()
+			.map(|e| e.index)
+			.collect()
+	}
+
+	#[test]
+	fn number_is_always_an_index() {
+		let entries = entries();
+
+		assert_eq!(indexes(&entries, &[EntryType::Sink], "2"), vec![2]);
+		assert!(matches!(
+			matching_entries(entries.iter(), &[EntryType::Sink], "3"),
+			Err(CommandError::NoMatch(_))
+		));
+	}
+
+	#[test]
+	fn exact_name_beats_regex() {
+		let entries = entries();
+
+		assert_eq!(
+			indexes(&entries, &[EntryType::SinkInput], "firefox"),
+			vec![3]
+		);
+		assert_eq!(
+			indexes(&entries, &[EntryType::Sink], "bluez_sink.headphones"),
+			vec![2]
+		);
+	}
+
+	#[test]
+	fn regex_when_no_name_matches() {
+		let entries = entries();
+
+		assert_eq!(
+			indexes(&entries, &[EntryType::SinkInput], "fire.*"),
+			vec![3, 4]
+		);
+		assert_eq!(indexes(&entries, &[EntryType::Sink], "^alsa_"), vec![1]);
+		assert!(matches!(
+			matching_entries(entries.iter(), &[EntryType::SinkInput], "^fox"),
+			Err(CommandError::NoMatch(_))
+		));
+	}
+
+	#[test]
+	fn only_given_types_match() {
+		let entries = entries();
+
+		assert_eq!(indexes(&entries, &[EntryType::Source], "firefox"), vec![5]);
+		assert_eq!(
+			indexes(
+				&entries,
+				&[EntryType::SinkInput, EntryType::Source],
+				"firefox"
+			),
+			vec![3, 5]
+		);
+	}
+
+	#[test]
+	fn invalid_regex() {
+		let entries = entries();
+
+		assert!(matches!(
+			matching_entries(entries.iter(), &[EntryType::Sink], "(head"),
+			Err(CommandError::InvalidPattern(_))
+		));
+	}
+}
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, io::Write, thread, time::Duration};
+
+use serde::Serialize;
+use tokio::time;
+
+use super::Subscription;
+use crate::{
+	cli_options::WatchOptions,
+	entry::{Entries, Entry, EntryIdentifier, EntryType, MeterLevel},
+	models::{EntryUpdate, ServerInfo},
+	pa::{self, common::MonitorTargets},
+	prelude::*,
+};
+
+static LOGGING_MODULE: &str = "Watch";
+
+const DEFAULT_PEAK_INTERVAL: u64 = 200;
+
+// one line of output
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+enum Event<'a> {
+	New(&'a Entry),
+	Change(&'a Entry),
+	Remove(EntryIdentifier),
+	DefaultSink { name: Option<&'a str> },
+	DefaultSource { name: Option<&'a str> },
+	Peaks { levels: Vec<Levels<'a>> },
+}
+
+#[derive(Serialize)]
+struct Levels<'a> {
+	#[serde(flatten)]
+	ident: &'a EntryIdentifier,
+	channels: &'a [MeterLevel],
+}
+
+#[derive(Default)]
+struct Watch {
+	entries: Entries,
+	server_info: ServerInfo,
+	// loudest levels since peaks were last printed
+	peaks: HashMap<EntryIdentifier, Vec<MeterLevel>>,
+	monitors: MonitorTargets,
+}
+
+pub async fn watch(server: Option<String>, opts: &WatchOptions) -> Result<()> {
+	let mut subscription = Subscription::connect(server.as_deref())?;
+
+	// meters use their own connection, the same way the TUI does
+	let monitors = if opts.peaks {
+		let (targets_sx, targets_rx) = cb_channel::unbounded();
+		let actions_sx = subscription.actions_sx();
+		let handle = thread::spawn(move || {
+			pa::start_monitors(targets_rx, actions_sx, server);
+		});
+
+		Some((targets_sx, handle))
+	} else {
+		None
+	};
+
+	let peak_interval = opts.peak_interval.unwrap_or(DEFAULT_PEAK_INTERVAL).max(1);
+	let mut peaks_tick = time::interval(Duration::from_millis(peak_interval));
+
+	let mut watch = Watch::default();
+
+	let res = loop {
+		tokio::select! {
+			update = subscription.next() => {
+				let update = match update {
+					Ok(update) => update,
+					Err(err) => break Err(err),
+				};
+				if !watch.update(update) {
+					break Ok(());
+				}
+
+				if let Some((targets_sx, _)) = &monitors {
+					let targets = watch.monitor_targets();
+					if targets != watch.monitors {
+						let _ = targets_sx.send(targets.clone());
+						watch.monitors = targets;
+					}
+				}
+			}
+			_ = peaks_tick.tick() => {
+				if !watch.print_peaks() {
+					break Ok(());
+				}
+			}
+		}
+	};
+
+	if let Some((targets_sx, handle)) = monitors {
+		drop(targets_sx);
+		let _ = handle.join();
+	}
+
+	res
+}
+
+// `false` once nobody reads the output anymore
+fn print(event: &Event) -> bool {
+	match serde_json::to_string(event) {
+		Ok(line) => writeln!(std::io::stdout(), "{line}").is_ok(),
+		Err(err) => {
+			warn!("{:?}", err);
+			true
+		}
+	}
+}
+
+impl Watch {
+	fn update(&mut self, update: EntryUpdate) -> bool {
+		match update {
+			EntryUpdate::EntryUpdate(ident, entry) => {
+				let mut entry = *entry;
+				let entry_type = entry.entry_type;
+				if let Some(play) = entry.entry_kind.play_entry_mut() {
+					play.is_default = self.server_info.is_default(entry_type, &play.device_name);
+				}
+
+				// entries are often requested again without anything changing
+				let event = match self.entries.get(&ident) {
+					Some(old) if *old == entry => {
+						return true;
+					}
+					Some(_) => Event::Change(&entry),
+					None => Event::New(&entry),
+				};
+				let printed = print(&event);
+
+				self.entries.insert(ident, entry);
+
+				printed
+			}
+			EntryUpdate::EntryRemoved(ident) => {
+				self.peaks.remove(&ident);
+
+				match self.entries.remove(&ident) {
+					Some(_) => print(&Event::Remove(ident)),
+					None => true,
+				}
+			}
+			EntryUpdate::ServerInfo(info) => {
+				let mut printed = true;
+				if info.default_sink != self.server_info.default_sink {
+					printed &= print(&Event::DefaultSink {
+						name: info.default_sink.as_deref(),
+					});
+				}
+				if info.default_source != self.server_info.default_source {
+					printed &= print(&Event::DefaultSource {
+						name: info.default_source.as_deref(),
+					});
+				}
+
+				self.server_info = info;
+
+				// devices that became or stopped being the default changed too
+				for entry_type in [EntryType::Sink, EntryType::Source] {
+					for (_, entry) in self.entries.iter_type_mut(entry_type) {
+						let flipped = match entry.entry_kind.play_entry_mut() {
+							Some(play) => {
+								let is_default =
+									self.server_info.is_default(entry_type, &play.device_name);
+								let flipped = play.is_default != is_default;
+								play.is_default = is_default;
+
+								flipped
+							}
+							None => false,
+						};
+
+						if flipped {
+							printed &= print(&Event::Change(entry));
+						}
+					}
+				}
+
+				printed
+			}
+			EntryUpdate::PeakVolumeUpdates(levels) => {
+				for (ident, levels) in levels {
+					let play = match self.entries.get_play_entry(&ident) {
+						Some(play) => play,
+						None => continue,
+					};
+					// what a recording stream receives, as in its meter in the TUI
+					let levels = if ident.entry_type == EntryType::SourceOutput {
+						play.received_levels(&levels)
+					} else {
+						levels
+					};
+
+					self.peaks
+						.entry(ident)
+						.and_modify(|old| {
+							for (old, new) in old.iter_mut().zip(&levels) {
+								old.peak = old.peak.max(new.peak);
+								old.rms = old.rms.max(new.rms);
+							}
+						})
+						.or_insert(levels);
+				}
+
+				true
+			}
+		}
+	}
+
+	fn print_peaks(&mut self) -> bool {
+		if self.peaks.is_empty() {
+			return true;
+		}
+
+		let mut idents: Vec<&EntryIdentifier> = self.peaks.keys().collect();
+		idents.sort_by_key(|ident| (ident.entry_type, ident.index));
+
+		let levels = idents
+			.into_iter()
+			.map(|ident| Levels {
+				ident,
+				channels: &self.peaks[ident],
+			})
+			.collect();
+		let printed = print(&Event::Peaks { levels });
+
+		self.peaks.clear();
+
+		printed
+	}
+
+	fn monitor_targets(&self) -> MonitorTargets {
+		let mut targets = HashMap::new();
+
+		for entry_type in [
+			EntryType::Sink,
+			EntryType::Source,
+			EntryType::SinkInput,
+			EntryType::SourceOutput,
+		] {
+			for (ident, entry) in self.entries.iter_type(entry_type) {
+				if let Some(play) = entry.entry_kind.play_entry() {
+					targets.insert(
+						*ident,
+						(entry.monitor_source(&self.entries), play.monitor_channels()),
+					);
+				}
+			}
+		}
+
+		targets
+	}
+}
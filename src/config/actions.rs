@@ -13,8 +13,11 @@ impl ToString for UserAction {
 			UserAction::ChangePage(PageType::Output) => "show_output".to_string(),
 			UserAction::ChangePage(PageType::Input) => "show_input".to_string(),
 			UserAction::ChangePage(PageType::Cards) => "show_cards".to_string(),
+			UserAction::ChangePage(PageType::Modules) => "show_modules".to_string(),
+			UserAction::ChangePage(PageType::Clients) => "show_clients".to_string(),
 			UserAction::OpenContextMenu(_) => "context_menu".to_string(),
 			UserAction::ShowHelp => "help".to_string(),
+			UserAction::ShowServerInfo => "server_info".to_string(),
 			UserAction::RequstChangeVolume(num, _) => {
 				if *num < 0 {
 					format!("lower_volume({num})")
@@ -22,6 +25,14 @@ impl ToString for UserAction {
 					format!("raise_volume({num})")
 				}
 			}
+			UserAction::RequestChangeBalance(num, _) => {
+				if *num < 0 {
+					format!("balance_left({})", -num)
+				} else {
+					format!("balance_right({num})")
+				}
+			}
+			UserAction::CenterBalance(_) => "center_balance".to_string(),
 			UserAction::MoveUp(num) => format!("up({num})"),
 			UserAction::MoveDown(num) => format!("down({num})"),
 			UserAction::MoveLeft => "left".to_string(),
@@ -33,13 +44,29 @@ impl ToString for UserAction {
 					"cycle_pages_backward".to_string()
 				}
 			}
+			UserAction::CycleServers(x) => {
+				if *x > 0 {
+					"cycle_servers_forward".to_string()
+				} else {
+					"cycle_servers_backward".to_string()
+				}
+			}
+			UserAction::CycleProfiles(x) => {
+				if *x > 0 {
+					"next_profile".to_string()
+				} else {
+					"previous_profile".to_string()
+				}
+			}
 			UserAction::CloseContextMenu => "close_context_menu".to_string(),
 			UserAction::Confirm => "confirm".to_string(),
 			UserAction::Hide(_) => "hide".to_string(),
 			UserAction::InputVolumeValue => "input_volume_value".to_string(),
-			UserAction::ChangeVolumeInputValue(_, _) | UserAction::SetSelected(_) => {
-				"unsupported".to_string()
-			}
+			UserAction::ToggleChannels(_) => "toggle_channels".to_string(),
+			UserAction::LoadModule => "load_module".to_string(),
+			UserAction::ChangeVolumeInputValue(_, _)
+			| UserAction::ChangeTextInputValue(_, _)
+			| UserAction::SetSelected(_) => "unsupported".to_string(),
 		}
 	}
 }
@@ -72,8 +99,12 @@ impl TryFrom<String> for UserAction {
 			"show_output" => UserAction::ChangePage(PageType::Output),
 			"show_input" => UserAction::ChangePage(PageType::Input),
 			"show_cards" => UserAction::ChangePage(PageType::Cards),
+			"show_modules" => UserAction::ChangePage(PageType::Modules),
+			"show_clients" => UserAction::ChangePage(PageType::Clients),
+			"load_module" => UserAction::LoadModule,
 			"context_menu" => UserAction::OpenContextMenu(None),
 			"help" => UserAction::ShowHelp,
+			"server_info" => UserAction::ShowServerInfo,
 			"lower_volume" => {
 				let a = match a.parse::<i16>() {
 					Ok(x) => x,
@@ -92,6 +123,25 @@ impl TryFrom<String> for UserAction {
 				};
 				UserAction::RequstChangeVolume(a, None)
 			}
+			"balance_left" => {
+				let a = match a.parse::<i16>() {
+					Ok(x) => x,
+					Err(_) => {
+						return Err(ConfigError::ActionBindingError(st.clone()));
+					}
+				};
+				UserAction::RequestChangeBalance(-a, None)
+			}
+			"balance_right" => {
+				let a = match a.parse::<i16>() {
+					Ok(x) => x,
+					Err(_) => {
+						return Err(ConfigError::ActionBindingError(st.clone()));
+					}
+				};
+				UserAction::RequestChangeBalance(a, None)
+			}
+			"center_balance" => UserAction::CenterBalance(None),
 			"up" => {
 				let a = match a.parse::<u16>() {
 					Ok(x) => x,
@@ -114,7 +164,12 @@ impl TryFrom<String> for UserAction {
 			"right" => UserAction::MoveRight,
 			"cycle_pages_forward" => UserAction::CyclePages(1),
 			"cycle_pages_backward" => UserAction::CyclePages(-1),
+			"cycle_servers_forward" => UserAction::CycleServers(1),
+			"cycle_servers_backward" => UserAction::CycleServers(-1),
+			"next_profile" => UserAction::CycleProfiles(1),
+			"previous_profile" => UserAction::CycleProfiles(-1),
 			"input_volume_value" => UserAction::InputVolumeValue,
+			"toggle_channels" => UserAction::ToggleChannels(None),
 			"close_context_menu" => UserAction::CloseContextMenu,
 			"confirm" => UserAction::Confirm,
 			"hide" => UserAction::Hide(None),
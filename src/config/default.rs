@@ -9,6 +9,7 @@ impl std::default::Default for RsMixerConfig {
 		bindings.insert("q".to_string(), "exit".to_string());
 		bindings.insert("ctrl+c".to_string(), "exit".to_string());
 		bindings.insert("?".to_string(), "help".to_string());
+		bindings.insert("i".to_string(), "server_info".to_string());
 
 		bindings.insert("j".to_string(), "down(1)".to_string());
 		bindings.insert("k".to_string(), "up(1)".to_string());
@@ -26,18 +27,31 @@ impl std::default::Default for RsMixerConfig {
 		bindings.insert("scroll_down".to_string(), "lower_volume(5)".to_string());
 		bindings.insert("scroll_up".to_string(), "raise_volume(5)".to_string());
 
+		bindings.insert("[".to_string(), "balance_left(5)".to_string());
+		bindings.insert("]".to_string(), "balance_right(5)".to_string());
+		bindings.insert("=".to_string(), "center_balance".to_string());
+
 		bindings.insert("m".to_string(), "mute".to_string());
+		bindings.insert("c".to_string(), "toggle_channels".to_string());
 		bindings.insert("mouse_middle".to_string(), "mute".to_string());
 		bindings.insert("mouse_right".to_string(), "mute".to_string());
 
 		bindings.insert("1".to_string(), "show_output".to_string());
 		bindings.insert("2".to_string(), "show_input".to_string());
 		bindings.insert("3".to_string(), "show_cards".to_string());
+		bindings.insert("4".to_string(), "show_modules".to_string());
+		bindings.insert("5".to_string(), "show_clients".to_string());
 		bindings.insert("F1".to_string(), "show_output".to_string());
 		bindings.insert("F2".to_string(), "show_input".to_string());
 		bindings.insert("F3".to_string(), "show_cards".to_string());
+		bindings.insert("F4".to_string(), "show_modules".to_string());
+		bindings.insert("F5".to_string(), "show_clients".to_string());
 		bindings.insert("tab".to_string(), "cycle_pages_forward".to_string());
 		bindings.insert("shift+tab".to_string(), "cycle_pages_backward".to_string());
+		bindings.insert("s".to_string(), "cycle_servers_forward".to_string());
+		bindings.insert("shift+s".to_string(), "cycle_servers_backward".to_string());
+		bindings.insert("p".to_string(), "next_profile".to_string());
+		bindings.insert("shift+p".to_string(), "previous_profile".to_string());
 
 		bindings.insert("enter".to_string(), "context_menu".to_string());
 		bindings.insert("enter".to_string(), "confirm".to_string());
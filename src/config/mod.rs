@@ -6,14 +6,14 @@ mod errors;
 pub mod keys_mouse;
 mod variables;
 
-use std::{collections::HashMap, convert::TryFrom};
+use std::{collections::HashMap, convert::TryFrom, env, path::PathBuf};
 
 use crossterm::style::{Attribute, ContentStyle};
 pub use errors::ConfigError;
 use linked_hash_map::LinkedHashMap;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-pub use variables::Variables;
+pub use variables::{MeterMode, MeterSettings, Variables, VolumeDisplay};
 
 use crate::{
 	models::{InputEvent, UserAction},
@@ -36,6 +36,20 @@ pub struct PulseAudio {
 	retry_time: Option<u64>,
 	rate: Option<u32>,
 	frag_size: Option<u32>,
+	max_volume: Option<u16>,
+	device_max_volume: Option<LinkedHashMap<String, u16>>,
+	volume_display: Option<String>,
+	stream_name: Option<String>,
+	server: Option<String>,
+	servers: Option<Vec<String>>,
+	meter: Option<String>,
+	meter_scale: Option<String>,
+	meter_attack: Option<u32>,
+	meter_decay: Option<f32>,
+	peak_hold: Option<u32>,
+	clip_indicator: Option<bool>,
+	per_channel_meters: Option<bool>,
+	control_socket: Option<String>,
 }
 
 impl PulseAudio {
@@ -51,6 +65,69 @@ impl PulseAudio {
 	pub fn frag_size(&self) -> u32 {
 		self.frag_size.unwrap_or(48)
 	}
+	// volumes are edited as i16 percentages, anything above that would wrap
+	pub fn max_volume(&self) -> u16 {
+		self.max_volume.unwrap_or(150).min(i16::MAX as u16)
+	}
+	pub fn volume_display(&self) -> VolumeDisplay {
+		match self.volume_display.as_deref() {
+			Some("percent") => VolumeDisplay::Percent,
+			Some("db") => VolumeDisplay::Db,
+			_ => VolumeDisplay::Both,
+		}
+	}
+	pub fn server(&self) -> Option<String> {
+		self.server.clone().filter(|s| !s.is_empty())
+	}
+	// `servers` takes precedence over `server`, "default" (or "") means the local server
+	pub fn servers(&self) -> Vec<Option<String>> {
+		match &self.servers {
+			Some(servers) if !servers.is_empty() => servers
+				.iter()
+				.map(|s| Some(s.clone()).filter(|s| !s.is_empty() && s != "default"))
+				.collect(),
+			_ => vec![self.server()],
+		}
+	}
+	pub fn stream_name(&self) -> String {
+		self.stream_name
+			.clone()
+			.unwrap_or_else(|| "{app}: {media}".to_string())
+	}
+	pub fn meter(&self) -> MeterSettings {
+		MeterSettings {
+			mode: match self.meter.as_deref() {
+				Some("rms") => MeterMode::Rms,
+				_ => MeterMode::Peak,
+			},
+			dbfs: self.meter_scale.as_deref() == Some("dbfs"),
+			attack_ms: self.meter_attack.unwrap_or(0),
+			decay_db: self.meter_decay.unwrap_or(20.0).max(0.0),
+			hold_ms: self.peak_hold.unwrap_or(1000),
+			clip_indicator: self.clip_indicator.unwrap_or(true),
+			per_channel: self.per_channel_meters.unwrap_or(false),
+		}
+	}
+	// "" turns the socket off, by default it lives in `$XDG_RUNTIME_DIR` and is
+	// off without it, as there's no other directory private to the user
+	pub fn control_socket(&self) -> Option<PathBuf> {
+		match self.control_socket.as_deref() {
+			Some("") => None,
+			Some(path) => Some(PathBuf::from(path)),
+			None => env::var_os("XDG_RUNTIME_DIR")
+				.filter(|dir| !dir.is_empty())
+				.map(|dir| PathBuf::from(dir).join("rsmixer.sock")),
+		}
+	}
+	pub fn device_max_volume(&self) -> Vec<(String, u16)> {
+		match &self.device_max_volume {
+			Some(m) => m
+				.iter()
+				.map(|(k, v)| (k.clone(), (*v).min(i16::MAX as u16)))
+				.collect(),
+			None => Vec::new(),
+		}
+	}
 }
 
 #[derive(Serialize, Deserialize, Clone)]
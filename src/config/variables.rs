@@ -1,10 +1,47 @@
+use std::path::PathBuf;
+
 use super::{PulseAudio, RsMixerConfig};
+use crate::util::matches_pattern;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VolumeDisplay {
+	Percent,
+	Db,
+	Both,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MeterMode {
+	Peak,
+	Rms,
+}
+
+// how live meters measure and show levels
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MeterSettings {
+	pub mode: MeterMode,
+	pub dbfs: bool,
+	pub attack_ms: u32,
+	// dB per second
+	pub decay_db: f32,
+	pub hold_ms: u32,
+	pub clip_indicator: bool,
+	// one meter per channel instead of a single mono one
+	pub per_channel: bool,
+}
 
 pub struct Variables {
 	pub pa_retry_time: u64,
 	pub pa_disable_live_volume: bool,
 	pub pa_rate: u32,
 	pub pa_frag_size: u32,
+	pub pa_max_volume: u16,
+	pub pa_device_max_volume: Vec<(String, u16)>,
+	pub volume_display: VolumeDisplay,
+	pub stream_name: String,
+	pub pa_servers: Vec<Option<String>>,
+	pub meter: MeterSettings,
+	pub control_socket: Option<PathBuf>,
 }
 
 impl Variables {
@@ -20,6 +57,26 @@ impl Variables {
 			pa_rate: pulse.rate(),
 			pa_frag_size: pulse.frag_size(),
 			pa_disable_live_volume: pulse.disable_live_volume(),
+			pa_max_volume: pulse.max_volume(),
+			pa_device_max_volume: pulse.device_max_volume(),
+			volume_display: pulse.volume_display(),
+			stream_name: pulse.stream_name(),
+			pa_servers: pulse.servers(),
+			meter: pulse.meter(),
+			control_socket: pulse.control_socket(),
 		}
 	}
+
+	// first device pattern matching the name wins, otherwise the global limit
+	pub fn max_volume(&self, device_name: Option<&str>) -> u16 {
+		if let Some(name) = device_name {
+			for (pattern, max) in &self.pa_device_max_volume {
+				if matches_pattern(pattern, name) {
+					return *max;
+				}
+			}
+		}
+
+		self.pa_max_volume
+	}
 }
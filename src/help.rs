@@ -97,10 +97,21 @@ pub fn generate() -> Vec<HelpLine> {
 			],
 		))
 	}
+	categories.push((
+		"Change balance".to_string(),
+		vec![
+			ActionMatcher::Any(UserAction::RequestChangeBalance(0, None)),
+			ActionMatcher::Any(UserAction::CenterBalance(None)),
+		],
+	));
 	categories.push((
 		"Mute/unmute".to_string(),
 		vec![ActionMatcher::Concrete(UserAction::RequestMute(None))],
 	));
+	categories.push((
+		"Show channels".to_string(),
+		vec![ActionMatcher::Any(UserAction::ToggleChannels(None))],
+	));
 	categories.push((
 		"Change page".to_string(),
 		vec![ActionMatcher::Any(UserAction::ChangePage(PageType::Output))],
@@ -109,6 +120,18 @@ pub fn generate() -> Vec<HelpLine> {
 		"Cycle pages".to_string(),
 		vec![ActionMatcher::Any(UserAction::CyclePages(0))],
 	));
+	categories.push((
+		"Switch server".to_string(),
+		vec![ActionMatcher::Any(UserAction::CycleServers(0))],
+	));
+	categories.push((
+		"Switch card profile".to_string(),
+		vec![ActionMatcher::Any(UserAction::CycleProfiles(0))],
+	));
+	categories.push((
+		"Server info".to_string(),
+		vec![ActionMatcher::Any(UserAction::ShowServerInfo)],
+	));
 	categories.push((
 		"Context menu".to_string(),
 		vec![ActionMatcher::Any(UserAction::OpenContextMenu(None))],
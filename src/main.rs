@@ -9,6 +9,7 @@ mod action_handlers;
 mod actor_system;
 mod actors;
 mod cli_options;
+mod commands;
 mod config;
 mod help;
 mod models;
@@ -21,7 +22,7 @@ mod util;
 use std::collections::HashMap;
 
 use actors::*;
-use cli_options::CliOptions;
+use cli_options::{CliOptions, Command};
 use config::{RsMixerConfig, Variables};
 use crossterm::style::ContentStyle;
 use lazy_static::lazy_static;
@@ -41,34 +42,36 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub type Styles = HashMap<Style, ContentStyle>;
 
-fn load_config_and_options() -> Result<()> {
-	info!("Checking command line options and config");
-
-	CliOptions::check()?;
-	debug!("CLI options checked");
+fn load_config(opts: CliOptions) -> Result<Option<Command>> {
+	info!("Checking config");
 
 	let mut config = RsMixerConfig::load()?;
-	let (styles, bindings, variables) = config.interpret()?;
+	let (styles, bindings, mut variables) = config.interpret()?;
+
+	if !opts.server.is_empty() {
+		variables.pa_servers = opts.server.into_iter().map(Some).collect();
+	}
 
 	STYLES.set(styles);
 	BINDINGS.set(bindings);
 	VARIABLES.set(variables);
 	debug!("Config loaded");
 
-	Ok(())
+	Ok(opts.command)
 }
 
 async fn run() -> Result<()> {
-	load_config_and_options()?;
-
 	debug!("Starting actor system");
 	let (mut context, worker) = actor_system::new();
 
 	let actor_system_handle = worker.start();
 
 	EventLoopActor::item().register_and_start(&mut context);
-	PulseActor::item().register_and_start(&mut context);
+	for server in 0..(*VARIABLES).get().pa_servers.len() {
+		PulseActor::item(server).register_and_start(&mut context);
+	}
 	InputActor::item().register_and_start(&mut context);
+	ControlActor::item().register_and_start(&mut context);
 
 	debug!("Actor system started");
 	actor_system_handle.await?
@@ -77,7 +80,36 @@ async fn run() -> Result<()> {
 fn main() -> Result<()> {
 	info!("Starting RsMixer");
 
-	let threaded_rt = runtime::Builder::new_multi_thread().enable_time().build()?;
+	let opts = match CliOptions::check() {
+		Ok(opts) => opts,
+		Err(e) => {
+			println!("{e:#?}");
+			return Ok(());
+		}
+	};
+	debug!("CLI options checked");
+
+	// commands run without the TUI and exit with their own status code
+	let has_command = opts.command.is_some();
+	let command = match load_config(opts) {
+		Ok(command) => command,
+		Err(e) if has_command => {
+			eprintln!("rsmixer: {e:#}");
+			std::process::exit(1);
+		}
+		Err(e) => {
+			println!("{e:#?}");
+			return Ok(());
+		}
+	};
+	if let Some(command) = command {
+		std::process::exit(commands::run(command));
+	}
+
+	let threaded_rt = runtime::Builder::new_multi_thread()
+		.enable_time()
+		.enable_io()
+		.build()?;
 	threaded_rt.block_on(async {
 		debug!("Tokio runtime started");
 
@@ -2,18 +2,19 @@ use std::collections::HashMap;
 
 use crossterm::event::Event;
 use pulse::volume::ChannelVolumes;
+use tokio::sync::mpsc;
 
 use crate::{
-	entry::{Entry, EntryIdentifier},
-	models::PageType,
+	entry::{Entry, EntryIdentifier, MeterLevel},
+	models::{PageType, ServerInfo},
 };
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum PAStatus {
-	// PulseAudio connection status
-	RetryIn(u64),
-	ConnectToPulseAudio,
-	PulseAudioDisconnected,
+	// PulseAudio connection status of the server with given id
+	RetryIn(usize, u64),
+	ConnectToPulseAudio(usize),
+	PulseAudioDisconnected(usize),
 }
 
 // redraw the whole screen (called every window resize)
@@ -25,12 +26,48 @@ impl ResizeScreen {
 	}
 }
 
+// lets meters fall while their monitors are quiet
+#[derive(Clone, PartialEq, Debug)]
+pub struct MeterTick {}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum EntryUpdate {
 	// entry updates
 	EntryRemoved(EntryIdentifier),
 	EntryUpdate(EntryIdentifier, Box<Entry>),
-	PeakVolumeUpdate(EntryIdentifier, f32),
+	// levels of every monitor that got data since the last batch, one per channel
+	PeakVolumeUpdates(Vec<(EntryIdentifier, Vec<MeterLevel>)>),
+	ServerInfo(ServerInfo),
+}
+impl EntryUpdate {
+	// updates come from a PulseAudio connection unaware of other servers
+	pub fn set_server(&mut self, server: usize, address: &Option<String>) {
+		match self {
+			EntryUpdate::EntryRemoved(ident) => {
+				ident.server = server;
+			}
+			EntryUpdate::PeakVolumeUpdates(levels) => {
+				for (ident, _) in levels {
+					ident.server = server;
+				}
+			}
+			EntryUpdate::EntryUpdate(ident, entry) => {
+				ident.server = server;
+				entry.entry_ident.server = server;
+			}
+			EntryUpdate::ServerInfo(info) => {
+				info.server = server;
+				info.address = address.clone();
+			}
+		}
+	}
+}
+
+// a line from the control socket, the answer goes to `reply`
+#[derive(Clone, Debug)]
+pub struct ControlRequest {
+	pub request: String,
+	pub reply: mpsc::UnboundedSender<String>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -54,6 +91,8 @@ pub enum UserAction {
 	ChangePage(PageType),
 	// positive - forwards, negative - backwards
 	CyclePages(i8),
+	CycleServers(i8),
+	CycleProfiles(i8),
 
 	// volume changes
 	RequestMute(Option<EntryIdentifier>),
@@ -62,8 +101,20 @@ pub enum UserAction {
 	// number of percentage points it should be changed by
 	RequstChangeVolume(i16, Option<EntryIdentifier>),
 
+	// request balance change where the argument is a number of
+	// percentage points it should be moved by (negative - to the left)
+	RequestChangeBalance(i16, Option<EntryIdentifier>),
+	CenterBalance(Option<EntryIdentifier>),
+
 	InputVolumeValue,
-	ChangeVolumeInputValue(String, u8),
+	ChangeVolumeInputValue(String, usize),
+
+	// open a text input for the name and arguments of a module to load
+	LoadModule,
+	ChangeTextInputValue(String, usize),
+
+	// show/hide per channel volume bars
+	ToggleChannels(Option<EntryIdentifier>),
 
 	// context menus
 	OpenContextMenu(Option<EntryIdentifier>),
@@ -71,6 +122,7 @@ pub enum UserAction {
 	Confirm,
 
 	ShowHelp,
+	ShowServerInfo,
 
 	Hide(Option<EntryIdentifier>),
 
@@ -84,8 +136,17 @@ pub enum PulseAudioAction {
 	MoveEntryToParent(EntryIdentifier, EntryIdentifier),
 	ChangeCardProfile(EntryIdentifier, String),
 	SetVolume(EntryIdentifier, ChannelVolumes),
-	CreateMonitors(HashMap<EntryIdentifier, Option<u32>>),
+	// monitor source and channel count of each monitored entry
+	CreateMonitors(HashMap<EntryIdentifier, (Option<u32>, u8)>),
 	SetSuspend(EntryIdentifier, bool),
 	KillEntry(EntryIdentifier),
+	SetDefaultSink(String),
+	SetDefaultSource(String),
+	SetPort(EntryIdentifier, String),
+	// card, port and offset in microseconds
+	SetPortLatencyOffset(EntryIdentifier, String, i64),
+	LoadModule(String, String),
+	UnloadModule(EntryIdentifier),
+	KillClient(u32),
 	Shutdown,
 }
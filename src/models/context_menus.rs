@@ -1,5 +1,6 @@
 use crate::{
 	actor_system::Ctx,
+	actors::pulse_actor_id,
 	entry::{Entry, EntryIdentifier, EntryKind, EntryType},
 	models::{PulseAudioAction, UserAction},
 	scrollable,
@@ -9,33 +10,70 @@ use crate::{
 #[derive(PartialEq, Clone)]
 pub enum ContextMenuOption {
 	MoveToEntry(EntryIdentifier, String),
-	ChangeCardProfile(String, String),
+	ChangeCardProfile(String, String, bool),
 	Kill,
 	Move,
 	Suspend,
 	Resume,
-	SetAsDefault,
+	SetAsDefault(String),
 	InputExactVolume,
+	Ports,
+	ChangePort(String, String, bool),
+	LoadModule,
+	UnloadModule,
+	KillClient(u32),
+	Details,
+	PortLatencyOffset(usize, String),
 }
 
 impl From<ContextMenuOption> for String {
 	fn from(option: ContextMenuOption) -> Self {
 		match option {
 			ContextMenuOption::MoveToEntry(_, s) => s,
-			ContextMenuOption::ChangeCardProfile(_, s) => s,
+			ContextMenuOption::ChangeCardProfile(_, s, available) => {
+				if available {
+					s
+				} else {
+					format!("{s} (unavailable)")
+				}
+			}
 			ContextMenuOption::Kill => "Kill".into(),
 			ContextMenuOption::Move => "Move".into(),
 			ContextMenuOption::Suspend => "Suspend".into(),
 			ContextMenuOption::Resume => "Resume".into(),
-			ContextMenuOption::SetAsDefault => "Set as default".into(),
+			ContextMenuOption::SetAsDefault(_) => "Set as default".into(),
 			ContextMenuOption::InputExactVolume => "Input exact volume value".into(),
+			ContextMenuOption::Ports => "Ports".into(),
+			ContextMenuOption::LoadModule => "Load module".into(),
+			ContextMenuOption::UnloadModule => "Unload".into(),
+			ContextMenuOption::KillClient(_) => "Disconnect client".into(),
+			ContextMenuOption::Details => "Details".into(),
+			ContextMenuOption::PortLatencyOffset(_, s) => s,
+			ContextMenuOption::ChangePort(_, s, available) => {
+				if available {
+					s
+				} else {
+					format!("{s} (unavailable)")
+				}
+			}
 		}
 	}
 }
 
+impl ContextMenuOption {
+	// unavailable profiles are shown, but can't be chosen
+	pub fn is_available(&self) -> bool {
+		!matches!(self, ContextMenuOption::ChangeCardProfile(_, _, false))
+	}
+}
+
 pub enum ContextMenuEffect {
 	None,
+	KeepOpen,
 	MoveEntry,
+	PortsMenu,
+	Details,
+	LatencyOffset(usize),
 }
 
 scrollable!(
@@ -70,38 +108,99 @@ pub struct ContextMenu {
 
 impl ContextMenu {
 	pub fn new(entry: &Entry) -> Self {
-		let play = match &entry.entry_kind {
-			EntryKind::PlayEntry(play) => Some(play),
-			EntryKind::CardEntry(_) => None,
-		};
-		let card = match &entry.entry_kind {
-			EntryKind::PlayEntry(_) => None,
-			EntryKind::CardEntry(card) => Some(card),
-		};
+		let play = entry.entry_kind.play_entry();
+		let card = entry.entry_kind.card_entry();
 		let options: Vec<ContextMenuOption> = match entry.entry_type {
-			EntryType::Source | EntryType::Sink => vec![
-				if play.unwrap().suspended {
+			EntryType::Source | EntryType::Sink => {
+				let play = play.unwrap();
+				let mut options = vec![if play.suspended {
 					ContextMenuOption::Resume
 				} else {
 					ContextMenuOption::Suspend
-				},
-				ContextMenuOption::SetAsDefault,
-				ContextMenuOption::InputExactVolume,
-			],
-			EntryType::SinkInput => vec![
-				ContextMenuOption::Move,
-				ContextMenuOption::Kill,
-				ContextMenuOption::InputExactVolume,
+				}];
+				if let (Some(name), false) = (&play.device_name, play.is_default) {
+					options.push(ContextMenuOption::SetAsDefault(name.clone()));
+				}
+				if !play.ports.is_empty() {
+					options.push(ContextMenuOption::Ports);
+				}
+				options.push(ContextMenuOption::InputExactVolume);
+				options.push(ContextMenuOption::Details);
+				options
+			}
+			EntryType::SinkInput => {
+				let mut options = vec![ContextMenuOption::Move, ContextMenuOption::Kill];
+				if let Some(client) = play.and_then(|p| p.client) {
+					options.push(ContextMenuOption::KillClient(client));
+				}
+				options.push(ContextMenuOption::InputExactVolume);
+				options.push(ContextMenuOption::Details);
+				options
+			}
+			EntryType::SourceOutput => {
+				let mut options = Vec::new();
+				if let Some(client) = play.and_then(|p| p.client) {
+					options.push(ContextMenuOption::KillClient(client));
+				}
+				options.push(ContextMenuOption::InputExactVolume);
+				options.push(ContextMenuOption::Details);
+				options
+			}
+			EntryType::Card => {
+				let card = card.unwrap();
+				card.profiles
+					.iter()
+					.map(|p| {
+						ContextMenuOption::ChangeCardProfile(
+							p.name.clone(),
+							p.description.clone(),
+							p.is_available(),
+						)
+					})
+					.chain(card.ports.iter().enumerate().map(|(i, p)| {
+						ContextMenuOption::PortLatencyOffset(
+							i,
+							format!(
+								"{}{}: latency offset {:+.1} ms",
+								p.description,
+								if p.available { "" } else { " (unavailable)" },
+								p.latency_offset as f64 / 1000.0
+							),
+						)
+					}))
+					.collect()
+			}
+			EntryType::Module => vec![
+				ContextMenuOption::UnloadModule,
+				ContextMenuOption::LoadModule,
 			],
-			EntryType::SourceOutput => vec![ContextMenuOption::InputExactVolume],
-			EntryType::Card => card
-				.unwrap()
-				.profiles
+			EntryType::Client => vec![ContextMenuOption::KillClient(entry.index)],
+		};
+
+		Self {
+			options,
+			selected: 0,
+			horizontal_scroll: 0,
+			area: Rect::default(),
+			tool_window: ToolWindowWidget::default(),
+			entry_ident: entry.entry_ident,
+		}
+	}
+
+	pub fn new_ports(entry: &Entry) -> Self {
+		let options = match &entry.entry_kind {
+			EntryKind::PlayEntry(play) => play
+				.ports
 				.iter()
 				.map(|p| {
-					ContextMenuOption::ChangeCardProfile(p.name.clone(), p.description.clone())
+					ContextMenuOption::ChangePort(
+						p.name.clone(),
+						p.description.clone(),
+						p.available,
+					)
 				})
 				.collect(),
+			_ => Vec::new(),
 		};
 
 		Self {
@@ -110,40 +209,97 @@ impl ContextMenu {
 			horizontal_scroll: 0,
 			area: Rect::default(),
 			tool_window: ToolWindowWidget::default(),
-			entry_ident: EntryIdentifier::new(entry.entry_type, entry.index),
+			entry_ident: entry.entry_ident,
 		}
 	}
 
 	pub fn resolve(&self, ident: EntryIdentifier, ctx: &Ctx) -> ContextMenuEffect {
+		if !self.options[self.selected].is_available() {
+			return ContextMenuEffect::KeepOpen;
+		}
+
 		match &self.options[self.selected] {
 			ContextMenuOption::Move => {
 				return ContextMenuEffect::MoveEntry;
 			}
+			ContextMenuOption::Ports => {
+				return ContextMenuEffect::PortsMenu;
+			}
+			ContextMenuOption::Details => {
+				return ContextMenuEffect::Details;
+			}
+			ContextMenuOption::PortLatencyOffset(port, _) => {
+				return ContextMenuEffect::LatencyOffset(*port);
+			}
+			ContextMenuOption::ChangePort(name, _, _) => {
+				ctx.send_to(
+					pulse_actor_id(ident.server),
+					PulseAudioAction::SetPort(ident, name.clone()),
+				);
+			}
 			ContextMenuOption::InputExactVolume => {
 				ctx.send_to("event_loop", UserAction::InputVolumeValue);
 			}
+			ContextMenuOption::LoadModule => {
+				ctx.send_to("event_loop", UserAction::LoadModule);
+			}
+			ContextMenuOption::UnloadModule => {
+				ctx.send_to(
+					pulse_actor_id(ident.server),
+					PulseAudioAction::UnloadModule(ident),
+				);
+			}
+			ContextMenuOption::KillClient(client) => {
+				ctx.send_to(
+					pulse_actor_id(ident.server),
+					PulseAudioAction::KillClient(*client),
+				);
+			}
 			ContextMenuOption::MoveToEntry(entry, _) => {
 				ctx.send_to(
-					"pulseaudio",
+					pulse_actor_id(ident.server),
 					PulseAudioAction::MoveEntryToParent(ident, *entry),
 				);
 			}
-			ContextMenuOption::ChangeCardProfile(name, _) => {
+			ContextMenuOption::ChangeCardProfile(name, _, _) => {
 				ctx.send_to(
-					"pulseaudio",
+					pulse_actor_id(ident.server),
 					PulseAudioAction::ChangeCardProfile(ident, name.clone()),
 				);
 			}
 			ContextMenuOption::Suspend => {
-				ctx.send_to("pulseaudio", PulseAudioAction::SetSuspend(ident, true));
+				ctx.send_to(
+					pulse_actor_id(ident.server),
+					PulseAudioAction::SetSuspend(ident, true),
+				);
 			}
 			ContextMenuOption::Resume => {
-				ctx.send_to("pulseaudio", PulseAudioAction::SetSuspend(ident, false));
+				ctx.send_to(
+					pulse_actor_id(ident.server),
+					PulseAudioAction::SetSuspend(ident, false),
+				);
 			}
 			ContextMenuOption::Kill => {
-				ctx.send_to("pulseaudio", PulseAudioAction::KillEntry(ident));
+				ctx.send_to(
+					pulse_actor_id(ident.server),
+					PulseAudioAction::KillEntry(ident),
+				);
 			}
-			_ => {}
+			ContextMenuOption::SetAsDefault(name) => match ident.entry_type {
+				EntryType::Sink => {
+					ctx.send_to(
+						pulse_actor_id(ident.server),
+						PulseAudioAction::SetDefaultSink(name.clone()),
+					);
+				}
+				EntryType::Source => {
+					ctx.send_to(
+						pulse_actor_id(ident.server),
+						PulseAudioAction::SetDefaultSource(name.clone()),
+					);
+				}
+				_ => {}
+			},
 		};
 
 		ContextMenuEffect::None
@@ -1,22 +1,73 @@
+use serde::Serialize;
+
 use crate::ui::Rect;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug, Serialize)]
 pub struct CardProfile {
 	pub name: String,
 	pub description: String,
 	#[cfg(feature = "pa_v13")]
 	pub available: bool,
+	pub priority: u32,
+	#[serde(skip)]
 	pub area: Rect,
+	#[serde(skip)]
 	pub is_selected: bool,
 }
 impl Eq for CardProfile {}
 
-#[derive(PartialEq, Clone, Debug)]
+impl CardProfile {
+	#[cfg(feature = "pa_v13")]
+	pub fn is_available(&self) -> bool {
+		self.available
+	}
+
+	// availability is only reported since PulseAudio 13
+	#[cfg(not(feature = "pa_v13"))]
+	pub fn is_available(&self) -> bool {
+		true
+	}
+}
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct CardPort {
+	pub name: String,
+	pub description: String,
+	pub available: bool,
+	// microseconds added to the latency of the sink/source while the port is active
+	pub latency_offset: i64,
+}
+impl Eq for CardPort {}
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
 pub struct CardEntry {
 	pub profiles: Vec<CardProfile>,
+	pub ports: Vec<CardPort>,
 	pub selected_profile: Option<usize>,
+	#[serde(skip)]
 	pub area: Rect,
+	#[serde(skip)]
 	pub is_selected: bool,
+	#[serde(skip)]
 	pub name: String,
 }
 impl Eq for CardEntry {}
+
+impl CardEntry {
+	// next available profile in the given direction, wrapping around
+	pub fn cycle_profile(&self, which_way: i8) -> Option<&CardProfile> {
+		let len = self.profiles.len() as isize;
+		let start = match self.selected_profile {
+			Some(i) => i as isize,
+			None if which_way < 0 => len,
+			None => -1,
+		};
+
+		(1..=len)
+			.map(|step| {
+				&self.profiles
+					[(start + step * which_way.signum() as isize).rem_euclid(len) as usize]
+			})
+			.find(|p| p.is_available())
+	}
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+use crate::ui::Rect;
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct ClientEntry {
+	#[serde(skip)]
+	pub name: String,
+	pub binary: Option<String>,
+	pub pid: Option<String>,
+	// number of sink inputs and source outputs owned by the client
+	pub streams: usize,
+	#[serde(skip)]
+	pub area: Rect,
+	#[serde(skip)]
+	pub is_selected: bool,
+}
+impl Eq for ClientEntry {}
@@ -1,10 +1,15 @@
-#[derive(Clone, Copy, PartialEq, Hash, Eq, Debug)]
+use serde::Serialize;
+
+#[derive(Clone, Copy, PartialEq, Hash, Eq, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum EntryType {
 	Sink,
 	SinkInput,
 	Source,
 	SourceOutput,
 	Card,
+	Module,
+	Client,
 }
 
 impl From<EntryType> for u8 {
@@ -15,6 +20,8 @@ impl From<EntryType> for u8 {
 			EntryType::SinkInput => 3,
 			EntryType::SourceOutput => 4,
 			EntryType::Card => 5,
+			EntryType::Module => 6,
+			EntryType::Client => 7,
 		}
 	}
 }
@@ -1,11 +1,16 @@
 use std::cmp::Ordering;
 
+use serde::Serialize;
+
 use super::EntryType;
 
-#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Serialize)]
 pub struct EntryIdentifier {
+	#[serde(rename = "type")]
 	pub entry_type: EntryType,
 	pub index: u32,
+	// position of the PulseAudio server in the configured server list
+	pub server: usize,
 }
 
 impl Eq for EntryIdentifier {}
@@ -32,7 +37,16 @@ impl std::cmp::Ord for EntryIdentifier {
 
 impl EntryIdentifier {
 	pub fn new(entry_type: EntryType, index: u32) -> Self {
-		Self { entry_type, index }
+		Self {
+			entry_type,
+			index,
+			server: 0,
+		}
+	}
+
+	pub fn with_server(mut self, server: usize) -> Self {
+		self.server = server;
+		self
 	}
 
 	pub fn is_card(&self) -> bool {
@@ -0,0 +1,131 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::config::{MeterMode, MeterSettings};
+
+// lowest level shown on the dBFS scale
+const DBFS_FLOOR: f32 = -60.0;
+// meter bars turn red above this level
+const RED_ABOVE_DBFS: f32 = -6.0;
+// how long the clip indicator stays lit after clipping
+const CLIP_HOLD: Duration = Duration::from_secs(2);
+// monitors of corked streams and suspended devices stop sending levels, after
+// this long without any the meter falls as if it got silence
+const SILENT_AFTER: Duration = Duration::from_millis(150);
+
+// levels of one monitor fragment, 1.0 is full scale
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize)]
+pub struct MeterLevel {
+	pub peak: f32,
+	pub rms: f32,
+}
+
+// level shown by a meter after applying ballistics
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Meter {
+	pub level: f32,
+	pub hold: f32,
+	pub clip: bool,
+	last_update: Option<Instant>,
+	last_input: Option<Instant>,
+	hold_since: Option<Instant>,
+	clip_since: Option<Instant>,
+}
+
+impl Meter {
+	pub fn update(&mut self, input: MeterLevel, settings: &MeterSettings, now: Instant) {
+		self.last_input = Some(now);
+
+		self.step(input, settings, now);
+	}
+
+	// called periodically, so the meter keeps moving while no levels arrive
+	pub fn advance(&mut self, settings: &MeterSettings, now: Instant) {
+		match self.last_input {
+			Some(since) if now.duration_since(since) >= SILENT_AFTER => {
+				self.step(MeterLevel::default(), settings, now);
+			}
+			_ => {}
+		}
+	}
+
+	fn step(&mut self, input: MeterLevel, settings: &MeterSettings, now: Instant) {
+		let value = match settings.mode {
+			MeterMode::Peak => input.peak,
+			MeterMode::Rms => input.rms,
+		};
+		let dt = self
+			.last_update
+			.map(|t| now.duration_since(t).as_secs_f32())
+			.unwrap_or(0.0);
+		self.last_update = Some(now);
+
+		if value >= self.level {
+			self.level = if settings.attack_ms == 0 {
+				value
+			} else {
+				let k = 1.0 - (-dt * 1000.0 / settings.attack_ms as f32).exp();
+				self.level + (value - self.level) * k
+			};
+		} else {
+			// falls at a constant rate in dB, but never below the current input
+			let fallen = to_db(self.level) - settings.decay_db * dt;
+			self.level = from_db(fallen).max(value);
+		}
+
+		let hold_expired = match self.hold_since {
+			Some(since) => now.duration_since(since).as_millis() >= settings.hold_ms as u128,
+			None => true,
+		};
+		if self.level >= self.hold || hold_expired {
+			self.hold = self.level;
+			self.hold_since = Some(now);
+		}
+
+		if input.peak >= 1.0 {
+			self.clip_since = Some(now);
+		}
+		self.clip = settings.clip_indicator
+			&& self
+				.clip_since
+				.is_some_and(|since| now.duration_since(since) < CLIP_HOLD);
+	}
+
+	// whether both meters are drawn the same way
+	pub fn looks_same(&self, other: &Meter) -> bool {
+		(self.level - other.level).abs() < f32::EPSILON
+			&& (self.hold - other.hold).abs() < f32::EPSILON
+			&& self.clip == other.clip
+	}
+
+	// position of a level on the meter bar, 0.0 - 1.0
+	pub fn fraction(value: f32, dbfs: bool) -> f32 {
+		if dbfs {
+			((to_db(value) - DBFS_FLOOR) / -DBFS_FLOOR).clamp(0.0, 1.0)
+		} else {
+			value.clamp(0.0, 1.0)
+		}
+	}
+
+	// `max` of the meter's VolumeWidget, so that the red segment starts at RED_ABOVE_DBFS
+	pub fn bar_max(dbfs: bool) -> f32 {
+		1.0 / Self::fraction(from_db(RED_ABOVE_DBFS), dbfs)
+	}
+}
+
+fn to_db(value: f32) -> f32 {
+	if value <= 0.0 {
+		DBFS_FLOOR
+	} else {
+		(20.0 * value.log10()).max(DBFS_FLOOR)
+	}
+}
+
+fn from_db(db: f32) -> f32 {
+	if db <= DBFS_FLOOR {
+		0.0
+	} else {
+		10f32.powf(db / 20.0)
+	}
+}
@@ -1,15 +1,25 @@
 mod card_entry;
+mod client_entry;
 mod entries;
 mod entry_type;
 mod identifier;
+mod meter;
+mod module_entry;
 mod play_entry;
+mod serialize;
 
-pub use card_entry::{CardEntry, CardProfile};
+use std::cmp::min;
+
+pub use card_entry::{CardEntry, CardPort, CardProfile};
+pub use client_entry::ClientEntry;
 pub use entries::Entries;
 pub use entry_type::EntryType;
 pub use identifier::EntryIdentifier;
-pub use play_entry::PlayEntry;
-use pulse::volume::ChannelVolumes;
+pub use meter::{Meter, MeterLevel};
+pub use module_entry::ModuleEntry;
+pub use play_entry::{Latency, PlayEntry, Port};
+use pulse::{channelmap::Map, volume::ChannelVolumes};
+use serde::Serialize;
 
 use crate::{
 	ui::{widgets::VolumeWidget, Rect},
@@ -24,37 +34,61 @@ pub enum EntrySpaceLvl {
 	MidChild,
 	LastChild,
 	Card,
+	Module,
+	Client,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[allow(clippy::large_enum_variant, clippy::enum_variant_names)]
+#[derive(PartialEq, Clone, Debug, Serialize)]
+#[serde(untagged)]
 pub enum EntryKind {
 	CardEntry(CardEntry),
 	PlayEntry(PlayEntry),
+	ModuleEntry(ModuleEntry),
+	ClientEntry(ClientEntry),
 }
 
 impl EntryKind {
 	pub fn play_entry(&self) -> Option<&PlayEntry> {
 		match self {
 			Self::PlayEntry(play) => Some(play),
-			Self::CardEntry(_) => None,
+			_ => None,
 		}
 	}
 	pub fn card_entry(&self) -> Option<&CardEntry> {
 		match self {
 			Self::CardEntry(card) => Some(card),
-			Self::PlayEntry(_) => None,
+			_ => None,
+		}
+	}
+	pub fn module_entry(&self) -> Option<&ModuleEntry> {
+		match self {
+			Self::ModuleEntry(module) => Some(module),
+			_ => None,
+		}
+	}
+	pub fn client_entry(&self) -> Option<&ClientEntry> {
+		match self {
+			Self::ClientEntry(client) => Some(client),
+			_ => None,
+		}
+	}
+	pub fn client_entry_mut(&mut self) -> Option<&mut ClientEntry> {
+		match self {
+			Self::ClientEntry(client) => Some(client),
+			_ => None,
 		}
 	}
 	pub fn play_entry_mut(&mut self) -> Option<&mut PlayEntry> {
 		match self {
 			Self::PlayEntry(play) => Some(play),
-			Self::CardEntry(_) => None,
+			_ => None,
 		}
 	}
 	pub fn card_entry_mut(&mut self) -> Option<&mut CardEntry> {
 		match self {
 			Self::CardEntry(card) => Some(card),
-			Self::PlayEntry(_) => None,
+			_ => None,
 		}
 	}
 }
@@ -86,14 +120,21 @@ impl HiddenStatus {
 	}
 }
 
-#[derive(PartialEq, Clone, Debug)]
+// serialized as one flat object: identifier, name and the fields of its kind
+#[derive(PartialEq, Clone, Debug, Serialize)]
 pub struct Entry {
+	#[serde(skip)]
 	pub entry_type: EntryType,
+	#[serde(flatten)]
 	pub entry_ident: EntryIdentifier,
+	#[serde(skip)]
 	pub index: u32,
 	pub name: String,
+	#[serde(skip)]
 	pub is_selected: bool,
+	#[serde(skip)]
 	pub position: EntrySpaceLvl,
+	#[serde(flatten)]
 	pub entry_kind: EntryKind,
 }
 impl Eq for Entry {}
@@ -123,6 +164,7 @@ impl Entry {
 		monitor_source: Option<u32>,
 		sink: Option<u32>,
 		suspended: bool,
+		device_name: Option<String>,
 	) -> Self {
 		Self {
 			entry_ident: EntryIdentifier::new(entry_type, index),
@@ -132,7 +174,7 @@ impl Entry {
 			is_selected: false,
 			position: EntrySpaceLvl::Empty,
 			entry_kind: EntryKind::PlayEntry(PlayEntry {
-				peak: 0.0,
+				meters: Vec::new(),
 				mute,
 				parent,
 				volume,
@@ -140,16 +182,91 @@ impl Entry {
 				sink,
 				volume_bar: VolumeWidget::default(),
 				peak_volume_bar: VolumeWidget::default(),
+				channel_meter_bars: Vec::new(),
 				suspended,
+				corked: false,
 				area: Rect::default(),
 				name,
 				is_selected: false,
 				position: EntrySpaceLvl::Empty,
 				hidden: HiddenStatus::Show,
+				device_name,
+				is_default: false,
+				ports: Vec::new(),
+				active_port: None,
+				channel_map: Map::default(),
+				expanded: false,
+				selected_channel: 0,
+				client: None,
+				media_name: None,
+				binary: None,
+				details: Vec::new(),
+				latency: None,
 			}),
 		}
 	}
 
+	pub fn ports(mut self, ports: Vec<Port>, active_port: Option<usize>) -> Self {
+		if let EntryKind::PlayEntry(play) = &mut self.entry_kind {
+			play.ports = ports;
+			play.active_port = active_port;
+		}
+		self
+	}
+
+	pub fn card_ports(mut self, ports: Vec<CardPort>) -> Self {
+		if let EntryKind::CardEntry(card) = &mut self.entry_kind {
+			card.ports = ports;
+		}
+		self
+	}
+
+	pub fn channel_map(mut self, channel_map: Map) -> Self {
+		if let EntryKind::PlayEntry(play) = &mut self.entry_kind {
+			play.channel_map = channel_map;
+		}
+		self
+	}
+
+	pub fn client(mut self, client: Option<u32>) -> Self {
+		if let EntryKind::PlayEntry(play) = &mut self.entry_kind {
+			play.client = client;
+		}
+		self
+	}
+
+	pub fn media(mut self, media_name: Option<String>, binary: Option<String>) -> Self {
+		if let EntryKind::PlayEntry(play) = &mut self.entry_kind {
+			play.media_name = media_name;
+			play.binary = binary;
+		}
+		self
+	}
+
+	pub fn latency(mut self, current: u64, configured: Option<u64>) -> Self {
+		if let EntryKind::PlayEntry(play) = &mut self.entry_kind {
+			play.latency = Some(Latency {
+				current,
+				configured,
+			});
+		}
+		self
+	}
+
+	pub fn corked(mut self, corked: bool) -> Self {
+		if let EntryKind::PlayEntry(play) = &mut self.entry_kind {
+			play.corked = corked;
+		}
+		self
+	}
+
+	pub fn details(mut self, details: Vec<(String, String)>) -> Self {
+		if let EntryKind::PlayEntry(play) = &mut self.entry_kind {
+			play.details = details;
+		}
+		self
+	}
+
 	pub fn new_card_entry(
 		index: u32,
 		name: String,
@@ -167,15 +284,63 @@ impl Entry {
 				area: Rect::default(),
 				is_selected: false,
 				profiles,
+				ports: Vec::new(),
 				selected_profile,
 				name,
 			}),
 		}
 	}
 
+	pub fn new_module_entry(
+		index: u32,
+		name: String,
+		argument: Option<String>,
+		n_used: Option<u32>,
+	) -> Self {
+		Self {
+			entry_ident: EntryIdentifier::new(EntryType::Module, index),
+			entry_type: EntryType::Module,
+			index,
+			name: name.clone(),
+			is_selected: false,
+			position: EntrySpaceLvl::Module,
+			entry_kind: EntryKind::ModuleEntry(ModuleEntry {
+				area: Rect::default(),
+				is_selected: false,
+				name,
+				argument,
+				n_used,
+			}),
+		}
+	}
+
+	pub fn new_client_entry(
+		index: u32,
+		name: String,
+		binary: Option<String>,
+		pid: Option<String>,
+	) -> Self {
+		Self {
+			entry_ident: EntryIdentifier::new(EntryType::Client, index),
+			entry_type: EntryType::Client,
+			index,
+			name: name.clone(),
+			is_selected: false,
+			position: EntrySpaceLvl::Client,
+			entry_kind: EntryKind::ClientEntry(ClientEntry {
+				area: Rect::default(),
+				is_selected: false,
+				name,
+				binary,
+				pid,
+				streams: 0,
+			}),
+		}
+	}
+
 	pub fn calc_area(position: EntrySpaceLvl, mut area: Rect) -> Rect {
 		let amount = match position {
-			EntrySpaceLvl::Card => 1,
+			EntrySpaceLvl::Card | EntrySpaceLvl::Module | EntrySpaceLvl::Client => 1,
 			EntrySpaceLvl::Parent => 2,
 			EntrySpaceLvl::ParentNoChildren => 2,
 			_ => 5,
@@ -196,7 +361,10 @@ impl Entry {
 			match self.entry_type {
 				EntryType::SinkInput => {
 					if let Some(sink) = play.sink {
-						match entries.get(&EntryIdentifier::new(EntryType::Sink, sink)) {
+						match entries.get(
+							&EntryIdentifier::new(EntryType::Sink, sink)
+								.with_server(self.entry_ident.server),
+						) {
 							Some(_) => play.monitor_source,
 							None => None,
 						}
@@ -217,12 +385,37 @@ impl Entry {
 				let old_card = unwrap_or_return!(entries.get_card_entry(&self.entry_ident), true);
 				old_card.name != card.name || old_card.selected_profile != card.selected_profile
 			}
+			EntryKind::ModuleEntry(module) => {
+				let old_module = unwrap_or_return!(
+					entries
+						.get(&self.entry_ident)
+						.and_then(|e| e.entry_kind.module_entry()),
+					true
+				);
+				old_module.name != module.name
+					|| old_module.argument != module.argument
+					|| old_module.n_used != module.n_used
+			}
+			EntryKind::ClientEntry(client) => {
+				let old_client = unwrap_or_return!(
+					entries
+						.get(&self.entry_ident)
+						.and_then(|e| e.entry_kind.client_entry()),
+					true
+				);
+				old_client.name != client.name
+					|| old_client.binary != client.binary
+					|| old_client.pid != client.pid
+			}
 			EntryKind::PlayEntry(play) => {
 				let old_play = unwrap_or_return!(entries.get_play_entry(&self.entry_ident), true);
 				old_play.name != play.name
+					|| old_play.media_name != play.media_name
 					|| old_play.mute != play.mute
 					|| old_play.volume != play.volume
-					|| (play.peak - old_play.peak).abs() < f32::EPSILON
+					|| old_play.is_default != play.is_default
+					|| old_play.latency != play.latency
+					|| (play.meter().level - old_play.meter().level).abs() < f32::EPSILON
 			}
 		}
 	}
@@ -234,11 +427,35 @@ impl Entry {
 					card.area = old_card.area;
 				}
 			}
+			EntryKind::ModuleEntry(module) => {
+				if let Some(old_module) = entries
+					.get(&self.entry_ident)
+					.and_then(|e| e.entry_kind.module_entry())
+				{
+					module.area = old_module.area;
+				}
+			}
+			EntryKind::ClientEntry(client) => {
+				if let Some(old_client) = entries
+					.get(&self.entry_ident)
+					.and_then(|e| e.entry_kind.client_entry())
+				{
+					client.area = old_client.area;
+					client.streams = old_client.streams;
+				}
+			}
 			EntryKind::PlayEntry(play) => {
 				if let Some(old_play) = entries.get_play_entry(&self.entry_ident) {
 					play.area = old_play.area;
 					play.volume_bar = old_play.volume_bar;
 					play.peak_volume_bar = old_play.peak_volume_bar;
+					play.channel_meter_bars = old_play.channel_meter_bars.clone();
+					play.meters = old_play.meters.clone();
+					play.expanded = old_play.expanded;
+					play.selected_channel = min(
+						old_play.selected_channel,
+						(play.volume.len() as usize).saturating_sub(1),
+					);
 				}
 			}
 		};
@@ -248,6 +465,8 @@ impl Entry {
 		match &self.entry_kind {
 			EntryKind::CardEntry(card) => card.area,
 			EntryKind::PlayEntry(play) => play.area,
+			EntryKind::ModuleEntry(module) => module.area,
+			EntryKind::ClientEntry(client) => client.area,
 		}
 	}
 }
@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+use crate::ui::Rect;
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct ModuleEntry {
+	#[serde(skip)]
+	pub name: String,
+	pub argument: Option<String>,
+	pub n_used: Option<u32>,
+	#[serde(skip)]
+	pub area: Rect,
+	#[serde(skip)]
+	pub is_selected: bool,
+}
+impl Eq for ModuleEntry {}
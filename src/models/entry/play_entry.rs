@@ -1,23 +1,126 @@
-use pulse::volume::ChannelVolumes;
+use pulse::{
+	channelmap::Map,
+	volume::{ChannelVolumes, VolumeLinear},
+};
 
-use super::{EntrySpaceLvl, HiddenStatus};
-use crate::ui::{widgets::VolumeWidget, Rect};
+use serde::Serialize;
 
-#[derive(PartialEq, Clone, Debug)]
+use super::{serialize, EntrySpaceLvl, HiddenStatus, Meter, MeterLevel};
+use crate::{
+	ui::{
+		widgets::{MeterWidget, VolumeWidget},
+		Rect,
+	},
+	VARIABLES,
+};
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct Port {
+	pub name: String,
+	pub description: String,
+	pub available: bool,
+}
+impl Eq for Port {}
+
+// in microseconds, the configured latency is only known for devices
+#[derive(PartialEq, Clone, Copy, Debug, Serialize)]
+pub struct Latency {
+	pub current: u64,
+	pub configured: Option<u64>,
+}
+
+// UI state is left out when serialized
+#[derive(PartialEq, Clone, Debug, Serialize)]
 pub struct PlayEntry {
-	pub peak: f32,
+	#[serde(skip)]
+	pub meters: Vec<Meter>,
 	pub mute: bool,
+	#[serde(serialize_with = "serialize::volumes")]
 	pub volume: ChannelVolumes,
 	pub monitor_source: Option<u32>,
 	pub sink: Option<u32>,
+	#[serde(skip)]
 	pub volume_bar: VolumeWidget,
+	#[serde(skip)]
 	pub peak_volume_bar: VolumeWidget,
+	#[serde(skip)]
+	pub channel_meter_bars: Vec<MeterWidget>,
 	pub suspended: bool,
+	pub corked: bool,
+	#[serde(skip)]
 	pub area: Rect,
+	#[serde(skip)]
 	pub name: String,
+	#[serde(skip)]
 	pub is_selected: bool,
+	#[serde(skip)]
 	pub position: EntrySpaceLvl,
+	#[serde(skip)]
 	pub hidden: HiddenStatus,
 	pub parent: Option<u32>,
+	pub device_name: Option<String>,
+	pub is_default: bool,
+	pub ports: Vec<Port>,
+	pub active_port: Option<usize>,
+	#[serde(serialize_with = "serialize::channel_map")]
+	pub channel_map: Map,
+	#[serde(skip)]
+	pub expanded: bool,
+	#[serde(skip)]
+	pub selected_channel: usize,
+	pub client: Option<u32>,
+	pub media_name: Option<String>,
+	pub binary: Option<String>,
+	pub details: Vec<(String, String)>,
+	pub latency: Option<Latency>,
 }
 impl Eq for PlayEntry {}
+
+impl PlayEntry {
+	pub fn meter(&self) -> Meter {
+		self.meters.first().copied().unwrap_or_default()
+	}
+
+	// channels of the monitor stream, all are mixed down to one unless configured otherwise
+	pub fn monitor_channels(&self) -> u8 {
+		if (*VARIABLES).get().meter.per_channel {
+			self.volume.len().max(1)
+		} else {
+			1
+		}
+	}
+
+	// per channel meters are stacked two to a row
+	pub fn stacked_meters(&self) -> bool {
+		self.monitor_channels() > 1
+	}
+
+	pub fn meter_rows(&self) -> u16 {
+		(self.monitor_channels() as u16).div_ceil(2)
+	}
+
+	// a source output is monitored through its whole source, what the stream
+	// receives also depends on its own volume, mute and whether it's corked
+	pub fn received_levels(&self, levels: &[MeterLevel]) -> Vec<MeterLevel> {
+		let volumes = self.volume.get();
+
+		levels
+			.iter()
+			.enumerate()
+			.map(|(i, level)| {
+				let gain = if self.mute || self.corked {
+					0.0
+				} else if levels.len() == volumes.len() {
+					VolumeLinear::from(volumes[i]).0 as f32
+				} else {
+					VolumeLinear::from(self.volume.max()).0 as f32
+				};
+
+				MeterLevel {
+					peak: level.peak * gain,
+					rms: level.rms * gain,
+				}
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,21 @@
+use pulse::{
+	channelmap::{Map, Position},
+	volume::ChannelVolumes,
+};
+use serde::Serializer;
+
+use crate::util::channel_volume_to_percent;
+
+// volume of each channel in percent
+pub fn volumes<S: Serializer>(volumes: &ChannelVolumes, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.collect_seq(volumes.get().iter().map(|v| channel_volume_to_percent(*v)))
+}
+
+// PulseAudio names of the channel positions, e.g. `front-left`
+pub fn channel_map<S: Serializer>(map: &Map, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.collect_seq(
+		map.get()
+			.iter()
+			.map(|p| Position::to_string(*p).unwrap_or_default()),
+	)
+}
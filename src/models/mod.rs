@@ -5,6 +5,7 @@ mod input_event;
 mod page_entries;
 mod page_type;
 mod redraw;
+mod server_info;
 mod state;
 mod style;
 mod ui_mode;
@@ -15,7 +16,8 @@ pub use input_event::InputEvent;
 pub use page_entries::PageEntries;
 pub use page_type::PageType;
 pub use redraw::Redraw;
+pub use server_info::ServerInfo;
 pub use style::Style;
 pub use ui_mode::UIMode;
 
-pub use self::state::RSState;
+pub use self::state::{server_label, RSState};
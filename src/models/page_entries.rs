@@ -8,6 +8,7 @@ pub struct PageEntries {
 	pub entries: Vec<EntryIdentifier>,
 	pub last_term_h: u16,
 	pub lvls: Vec<EntrySpaceLvl>,
+	pub channel_rows: Vec<u16>,
 	pub visibility: Vec<usize>,
 	selected: usize,
 }
@@ -18,6 +19,7 @@ impl PageEntries {
 			entries: Vec::new(),
 			last_term_h: 0,
 			lvls: Vec::new(),
+			channel_rows: Vec::new(),
 			visibility: Vec::new(),
 			selected: 0,
 		}
@@ -72,6 +74,14 @@ impl PageEntries {
 
 		ret
 	}
+
+	pub fn set_channel_rows(&mut self, channel_rows: Vec<u16>) -> bool {
+		let ret = self.channel_rows != channel_rows;
+
+		self.channel_rows = channel_rows;
+
+		ret
+	}
 }
 
 scrollable!(
@@ -92,7 +102,7 @@ scrollable!(
 	},
 	fn element_height(&self, index: usize) -> u16 {
 		if let Some(lvl) = self.lvls.get(index) {
-			entry_height(*lvl)
+			entry_height(*lvl) + self.channel_rows.get(index).unwrap_or(&0)
 		} else {
 			0
 		}
@@ -102,6 +112,10 @@ scrollable!(
 fn calc_lvl(parent_type: EntryType, vs: &[EntryIdentifier], index: usize) -> EntrySpaceLvl {
 	if parent_type == EntryType::Card {
 		EntrySpaceLvl::Card
+	} else if parent_type == EntryType::Module {
+		EntrySpaceLvl::Module
+	} else if parent_type == EntryType::Client {
+		EntrySpaceLvl::Client
 	} else if vs[index].entry_type == parent_type {
 		if index + 1 >= vs.len() || vs[index + 1].entry_type == parent_type {
 			EntrySpaceLvl::ParentNoChildren
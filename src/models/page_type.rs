@@ -8,6 +8,8 @@ pub enum PageType {
 	Output,
 	Input,
 	Cards,
+	Modules,
+	Clients,
 }
 impl Eq for PageType {}
 impl Display for PageType {
@@ -21,16 +23,20 @@ impl From<PageType> for i8 {
 			PageType::Output => 0,
 			PageType::Input => 1,
 			PageType::Cards => 2,
+			PageType::Modules => 3,
+			PageType::Clients => 4,
 		}
 	}
 }
 impl From<i8> for PageType {
 	fn from(p: i8) -> PageType {
 		match p {
-			-1 => PageType::Cards,
+			-1 => PageType::Clients,
 			0 => PageType::Output,
 			1 => PageType::Input,
 			2 => PageType::Cards,
+			3 => PageType::Modules,
+			4 => PageType::Clients,
 			_ => PageType::Output,
 		}
 	}
@@ -41,13 +47,21 @@ impl PageType {
 			Self::Output => (EntryType::Sink, EntryType::SinkInput),
 			Self::Input => (EntryType::Source, EntryType::SourceOutput),
 			Self::Cards => (EntryType::Card, EntryType::Card),
+			Self::Modules => (EntryType::Module, EntryType::Module),
+			Self::Clients => (EntryType::Client, EntryType::Client),
 		}
 	}
+	// pages listing sinks/sources and their streams
+	pub fn has_play_entries(&self) -> bool {
+		matches!(self, Self::Output | Self::Input)
+	}
 	pub fn as_str(&self) -> &'static str {
 		match self {
 			PageType::Output => "Output",
 			PageType::Input => "Input",
 			PageType::Cards => "Cards",
+			PageType::Modules => "Modules",
+			PageType::Clients => "Clients",
 		}
 	}
 	pub fn as_styled_string(&self) -> String {
@@ -76,8 +90,9 @@ impl PageType {
 		entries: &'a Entries,
 		ui_mode: &'a UIMode,
 	) -> Box<dyn Iterator<Item = (&'a EntryIdentifier, &'a Entry)> + 'a> {
-		if *self == PageType::Cards {
-			return Box::new(entries.iter_type(EntryType::Card));
+		if !self.has_play_entries() {
+			let (entry_type, _) = self.parent_child_types();
+			return Box::new(entries.iter_type(entry_type));
 		}
 
 		let (parent, child) = self.parent_child_types();
@@ -107,8 +122,8 @@ impl PageType {
 			std::iter::once((ident, entry)).chain(entries.iter_type(child).filter(move |(_, e)| {
 				e.parent() == Some(ident.index)
 					&& match &e.entry_kind {
-						EntryKind::CardEntry(_) => true,
 						EntryKind::PlayEntry(play) => play.hidden != HiddenStatus::Hidden,
+						_ => true,
 					}
 			}))
 		}))
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 #[derive(PartialEq, Debug, Clone, Default)]
 pub struct Redraw {
 	pub entries: bool,
-	pub peak_volume: Option<usize>,
+	pub peak_volumes: HashSet<usize>,
 	pub resize: bool,
 	pub affected_entries: HashSet<usize>,
 	pub context_menu: bool,
@@ -15,7 +15,7 @@ impl Redraw {
 	}
 	pub fn anything(&self) -> bool {
 		self.entries
-			|| self.peak_volume.is_some()
+			|| !self.peak_volumes.is_empty()
 			|| self.resize
 			|| self.context_menu
 			|| !self.affected_entries.is_empty()
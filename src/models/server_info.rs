@@ -0,0 +1,81 @@
+use serde::Serialize;
+
+use crate::entry::EntryType;
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize)]
+pub struct ServerInfo {
+	pub default_sink: Option<String>,
+	pub default_source: Option<String>,
+	pub server_name: Option<String>,
+	pub server_version: Option<String>,
+	pub host_name: Option<String>,
+	pub user_name: Option<String>,
+	pub sample_spec: Option<String>,
+	pub channel_map: Option<String>,
+	pub address: Option<String>,
+	pub server: usize,
+}
+
+impl ServerInfo {
+	pub fn is_default(&self, entry_type: EntryType, device_name: &Option<String>) -> bool {
+		let default = match entry_type {
+			EntryType::Sink => &self.default_sink,
+			EntryType::Source => &self.default_source,
+			_ => {
+				return false;
+			}
+		};
+
+		default.is_some() && default == device_name
+	}
+
+	// summary shown in the header, e.g. "PulseAudio (on PipeWire 1.0.5) 15.0.0 @ host"
+	// with the address of the server when not connected to the default one, followed by
+	// the default devices and sample spec; the header cuts off what doesn't fit
+	pub fn status_line(&self) -> Option<String> {
+		let name = self.server_name.as_ref()?;
+
+		let mut line = name.clone();
+		if let Some(version) = &self.server_version {
+			line = format!("{line} {version}");
+		}
+		if let Some(host) = &self.host_name {
+			line = format!("{line} @ {host}");
+		}
+		if let Some(address) = &self.address {
+			line = format!("{line} ({address})");
+		}
+		if let Some(sink) = &self.default_sink {
+			line = format!("{line} | sink: {sink}");
+		}
+		if let Some(source) = &self.default_source {
+			line = format!("{line} | source: {source}");
+		}
+		if let Some(spec) = &self.sample_spec {
+			line = format!("{line} | {spec}");
+		}
+
+		Some(line)
+	}
+
+	pub fn details(&self) -> Vec<(String, String)> {
+		let unknown = |x: &Option<String>| x.clone().unwrap_or_else(|| "-".to_string());
+
+		vec![
+			("Server".to_string(), unknown(&self.server_name)),
+			("Version".to_string(), unknown(&self.server_version)),
+			("Host".to_string(), unknown(&self.host_name)),
+			(
+				"Address".to_string(),
+				self.address
+					.clone()
+					.unwrap_or_else(|| "default".to_string()),
+			),
+			("User".to_string(), unknown(&self.user_name)),
+			("Default sink".to_string(), unknown(&self.default_sink)),
+			("Default source".to_string(), unknown(&self.default_source)),
+			("Sample spec".to_string(), unknown(&self.sample_spec)),
+			("Channel map".to_string(), unknown(&self.channel_map)),
+		]
+	}
+}
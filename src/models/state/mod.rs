@@ -1,18 +1,28 @@
 mod page_entries;
+mod servers;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, mem, time::Instant};
+
+use pulse::volume::{Volume, VolumeDB};
+
+pub use servers::{server_label, ServerState};
 
 use super::{
-	ContextMenu, ContextMenuEffect, PageEntries, PageType, PulseAudioAction, Redraw, UIMode,
+	ContextMenu, ContextMenuEffect, PageEntries, PageType, PulseAudioAction, Redraw, ServerInfo,
+	UIMode,
 };
 use crate::{
 	actor_system::Ctx,
-	entry::{Entries, Entry, EntryIdentifier, EntryKind},
+	actors::pulse_actor_id,
+	config::VolumeDisplay,
+	entry::{Entries, Entry, EntryIdentifier, EntryKind, EntryType, Meter, MeterLevel, PlayEntry},
 	ui::{
-		widgets::{HelpWidget, VolumeInputWidget, WarningTextWidget},
+		widgets::{HelpWidget, InfoWidget, TextInputWidget, VolumeInputWidget, WarningTextWidget},
 		Scrollable, UI,
 	},
-	util::{percent_to_volume, volume_to_percent},
+	unwrap_or_return,
+	util::{channel_volume_to_percent, parse_volume_input, percent_to_volume, volume_to_percent},
+	VARIABLES,
 };
 
 pub struct RSState {
@@ -25,6 +35,11 @@ pub struct RSState {
 	pub help: HelpWidget,
 	pub warning_text: WarningTextWidget,
 	pub input_exact_volume: VolumeInputWidget,
+	pub input_text: TextInputWidget,
+	pub server_info: ServerInfo,
+	pub info_panel: InfoWidget,
+	pub current_server: usize,
+	pub servers: Vec<ServerState>,
 	pub ui: UI,
 	pub ctx: Option<Ctx>,
 }
@@ -43,6 +58,11 @@ impl Default for RSState {
 				text: "".to_string(),
 			},
 			input_exact_volume: VolumeInputWidget::default(),
+			input_text: TextInputWidget::default(),
+			server_info: ServerInfo::default(),
+			info_panel: InfoWidget::default(),
+			current_server: 0,
+			servers: Vec::new(),
 			ui: UI::default(),
 			ctx: None,
 		}
@@ -60,19 +80,29 @@ impl RSState {
 			redraw: Redraw::default(),
 			help: HelpWidget::default(),
 			input_exact_volume: VolumeInputWidget::default(),
+			input_text: TextInputWidget::default(),
 			warning_text: WarningTextWidget {
 				text: "".to_string(),
 			},
+			server_info: ServerInfo::default(),
+			info_panel: InfoWidget::default(),
+			current_server: 0,
+			servers: servers::new_server_list(),
 			ui: UI::default(),
 			ctx: Some(ctx),
 		}
 	}
 	pub fn reset(&mut self) {
 		self.ctx().send_to(
-			"pulseaudio",
+			pulse_actor_id(self.current_server),
 			PulseAudioAction::CreateMonitors(HashMap::new()),
 		);
+		let servers = mem::take(&mut self.servers);
+		let current_server = self.current_server;
+
 		*self = Self::new(self.ctx.take().unwrap());
+		self.servers = servers;
+		self.current_server = current_server;
 		self.redraw.resize = true;
 	}
 	pub fn change_ui_mode(&mut self, mode: UIMode) {
@@ -81,18 +111,34 @@ impl RSState {
 		self.redraw.resize = true;
 	}
 	pub fn remove_entry(&mut self, ident: &EntryIdentifier) {
+		if ident.server != self.current_server {
+			self.stash_entry(ident, None);
+			return;
+		}
+
 		self.entries.remove(ident);
 
 		if self.page_entries.ident_position(*ident).is_some() {
 			page_entries::update(self);
 		}
 
-		if self.ui_mode == UIMode::ContextMenu {
+		if self.ui_mode == UIMode::ContextMenu || self.ui_mode == UIMode::Details(*ident) {
 			self.change_ui_mode(UIMode::Normal);
 		}
 	}
 
 	pub fn update_entry(&mut self, ident: &EntryIdentifier, mut entry: Entry) {
+		if ident.server != self.current_server {
+			self.stash_entry(ident, Some(entry));
+			return;
+		}
+
+		if let EntryKind::PlayEntry(play) = &mut entry.entry_kind {
+			play.is_default = self
+				.server_info
+				.is_default(entry.entry_type, &play.device_name);
+		}
+
 		if entry.needs_redraw(&self.entries) {
 			if let Some(i) = self
 				.page_entries
@@ -107,18 +153,129 @@ impl RSState {
 
 		self.entries.insert(*ident, entry);
 
+		if self.ui_mode == UIMode::Details(*ident) {
+			let lines = self.info_panel.lines.len();
+			self.refresh_details(ident);
+
+			if lines == self.info_panel.lines.len() {
+				self.redraw.context_menu = true;
+			} else {
+				self.redraw.resize = true;
+			}
+		}
+
 		page_entries::update(self);
 	}
 
-	pub fn update_peak_volume(&mut self, ident: &EntryIdentifier, peak: &f32) {
+	pub fn update_server_info(&mut self, server_info: ServerInfo) {
+		if server_info.server != self.current_server {
+			self.stash_server_info(server_info);
+			return;
+		}
+
+		if self.server_info.status_line() != server_info.status_line() {
+			self.redraw.resize = true;
+		}
+
+		self.server_info = server_info;
+
+		if self.ui_mode == UIMode::ServerInfo {
+			self.info_panel.set_lines(self.server_info.details());
+			self.redraw.resize = true;
+		}
+
+		self.update_default_flags();
+	}
+
+	fn update_default_flags(&mut self) {
+		for entry_type in [EntryType::Sink, EntryType::Source] {
+			for (_, entry) in self.entries.iter_type_mut(entry_type) {
+				if let EntryKind::PlayEntry(play) = &mut entry.entry_kind {
+					let is_default = self.server_info.is_default(entry_type, &play.device_name);
+
+					if play.is_default != is_default {
+						play.is_default = is_default;
+						self.redraw.entries = true;
+					}
+				}
+			}
+		}
+	}
+
+	pub fn show_details(&mut self, ident: EntryIdentifier) {
+		self.refresh_details(&ident);
+		self.info_panel.reset_scroll();
+		self.change_ui_mode(UIMode::Details(ident));
+	}
+
+	fn refresh_details(&mut self, ident: &EntryIdentifier) {
+		if let Some(play) = self.entries.get_play_entry(ident) {
+			let mut lines = vec![("Name".to_string(), play.name.clone())];
+			if let Some(device_name) = &play.device_name {
+				lines.push(("Device".to_string(), device_name.clone()));
+			}
+			lines.extend(play.details.iter().cloned());
+
+			self.info_panel.set_lines(lines);
+		}
+	}
+
+	pub fn show_server_info(&mut self) {
+		self.info_panel.set_lines(self.server_info.details());
+		self.info_panel.reset_scroll();
+		self.change_ui_mode(UIMode::ServerInfo);
+	}
+
+	pub fn update_peak_volume(&mut self, ident: &EntryIdentifier, levels: &[MeterLevel]) {
+		if ident.server != self.current_server {
+			return;
+		}
+
 		if let Some(play) = self.entries.get_play_entry_mut(ident) {
-			if (play.peak - peak).abs() < f32::EPSILON {
+			let levels = if ident.entry_type == EntryType::SourceOutput {
+				play.received_levels(levels)
+			} else {
+				levels.to_vec()
+			};
+			let now = Instant::now();
+			let old = play.meters.clone();
+			play.meters.resize(levels.len(), Meter::default());
+			for (meter, level) in play.meters.iter_mut().zip(&levels) {
+				meter.update(*level, &(*VARIABLES).get().meter, now);
+			}
+
+			if old.len() == play.meters.len()
+				&& old
+					.iter()
+					.zip(&play.meters)
+					.all(|(old, new)| old.looks_same(new))
+			{
 				return;
 			}
-			play.peak = *peak;
 
 			if let Some(i) = self.page_entries.iter_entries().position(|&i| *ident == i) {
-				self.redraw.peak_volume = Some(i);
+				self.redraw.peak_volumes.insert(i);
+			}
+		}
+	}
+
+	pub fn advance_meters(&mut self) {
+		let now = Instant::now();
+
+		for (i, ident) in self.page_entries.iter_entries().enumerate() {
+			if let Some(play) = self.entries.get_play_entry_mut(ident) {
+				let old = play.meters.clone();
+				for meter in &mut play.meters {
+					meter.advance(&(*VARIABLES).get().meter, now);
+				}
+
+				if old
+					.iter()
+					.zip(&play.meters)
+					.any(|(old, new)| !old.looks_same(new))
+				{
+					self.redraw.peak_volumes.insert(i);
+				}
 			}
 		}
 	}
@@ -143,6 +300,11 @@ impl RSState {
 
 				self.redraw.context_menu = true;
 			}
+			UIMode::ServerInfo | UIMode::Details(_) => {
+				self.info_panel.down(how_much);
+
+				self.redraw.context_menu = true;
+			}
 			UIMode::MoveEntry(_, _) => {
 				if self.page_entries.entries.len() < 2 {
 					return;
@@ -186,6 +348,11 @@ impl RSState {
 
 				self.redraw.context_menu = true;
 			}
+			UIMode::ServerInfo | UIMode::Details(_) => {
+				self.info_panel.up(how_much);
+
+				self.redraw.context_menu = true;
+			}
 			UIMode::MoveEntry(_, _) => {
 				if self.page_entries.entries.len() < 2 {
 					return;
@@ -215,7 +382,9 @@ impl RSState {
 	}
 
 	pub fn move_left(&mut self) {
-		if self.context_menu.horizontal_scroll > 0 {
+		if self.ui_mode == UIMode::Normal {
+			self.change_selected_channel(-1);
+		} else if self.context_menu.horizontal_scroll > 0 {
 			self.context_menu.horizontal_scroll -= 1;
 
 			self.redraw.context_menu = true;
@@ -223,13 +392,49 @@ impl RSState {
 	}
 
 	pub fn move_right(&mut self) {
-		if self.context_menu.horizontal_scroll < self.context_menu.max_horizontal_scroll() {
+		if self.ui_mode == UIMode::Normal {
+			self.change_selected_channel(1);
+		} else if self.context_menu.horizontal_scroll < self.context_menu.max_horizontal_scroll() {
 			self.context_menu.horizontal_scroll += 1;
 
 			self.redraw.context_menu = true;
 		}
 	}
 
+	fn change_selected_channel(&mut self, how_much: i32) {
+		let ident = unwrap_or_return!(self.page_entries.get_selected());
+		let play = unwrap_or_return!(self.entries.get_play_entry_mut(&ident));
+
+		if !play.expanded {
+			return;
+		}
+
+		let channels = play.volume.len() as i32;
+		play.selected_channel =
+			(play.selected_channel as i32 + how_much).rem_euclid(channels) as usize;
+
+		self.selected_entry_needs_redraw();
+	}
+
+	pub fn toggle_channels(&mut self, ident: &Option<EntryIdentifier>) {
+		let ident = match *ident {
+			Some(i) => i,
+			None => match self.page_entries.get_selected() {
+				Some(sel) => sel,
+				None => {
+					return;
+				}
+			},
+		};
+
+		if let Some(play) = self.entries.get_play_entry_mut(&ident) {
+			play.expanded = !play.expanded;
+			play.selected_channel = 0;
+		}
+
+		page_entries::update(self);
+	}
+
 	pub fn set_selected(&mut self, index: usize) {
 		match self.ui_mode {
 			UIMode::Normal => {
@@ -265,8 +470,10 @@ impl RSState {
 				return;
 			}
 		};
-		self.ctx()
-			.send_to("pulseaudio", PulseAudioAction::MuteEntry(ident, !mute));
+		self.ctx().send_to(
+			pulse_actor_id(self.current_server),
+			PulseAudioAction::MuteEntry(ident, !mute),
+		);
 	}
 
 	pub fn request_change_volume(&mut self, how_much: i16, ident: &Option<EntryIdentifier>) {
@@ -281,18 +488,88 @@ impl RSState {
 		};
 
 		if let Some(play) = self.entries.get_play_entry_mut(&ident) {
+			let mut vols = play.volume;
+			let max = max_volume(play);
+
+			if play.expanded {
+				if let Some(v) = vols.get_mut().get_mut(play.selected_channel) {
+					let target_percent = (channel_volume_to_percent(*v) as i16 + how_much).min(max);
+
+					v.0 = percent_to_volume(target_percent);
+				}
+			} else {
+				let target_percent = (volume_to_percent(vols) as i16 + how_much).min(max);
+
+				let target = percent_to_volume(target_percent);
+
+				for v in vols.get_mut() {
+					v.0 = target;
+				}
+			}
+
+			self.ctx().send_to(
+				pulse_actor_id(self.current_server),
+				PulseAudioAction::SetVolume(ident, vols),
+			);
+		}
+	}
+
+	pub fn request_change_balance(&mut self, how_much: i16, ident: &Option<EntryIdentifier>) {
+		let ident = match *ident {
+			Some(i) => i,
+			None => match self.page_entries.get_selected() {
+				Some(sel) => sel,
+				None => {
+					return;
+				}
+			},
+		};
+
+		if let Some(play) = self.entries.get_play_entry(&ident) {
+			if !play.channel_map.can_balance() {
+				return;
+			}
+
 			let mut vols = play.volume;
 
-			let target_percent = volume_to_percent(vols) as i16 + how_much;
+			let balance = vols.get_balance(&play.channel_map) + how_much as f32 / 100.0;
 
-			let target = percent_to_volume(target_percent);
+			if vols
+				.set_balance(&play.channel_map, balance.clamp(-1.0, 1.0))
+				.is_some()
+			{
+				self.ctx().send_to(
+					pulse_actor_id(self.current_server),
+					PulseAudioAction::SetVolume(ident, vols),
+				);
+			}
+		}
+	}
 
-			for v in vols.get_mut() {
-				v.0 = target;
+	pub fn request_center_balance(&mut self, ident: &Option<EntryIdentifier>) {
+		let ident = match *ident {
+			Some(i) => i,
+			None => match self.page_entries.get_selected() {
+				Some(sel) => sel,
+				None => {
+					return;
+				}
+			},
+		};
+
+		if let Some(play) = self.entries.get_play_entry(&ident) {
+			if !play.channel_map.can_balance() {
+				return;
 			}
 
-			self.ctx()
-				.send_to("pulseaudio", PulseAudioAction::SetVolume(ident, vols));
+			let mut vols = play.volume;
+
+			if vols.set_balance(&play.channel_map, 0.0).is_some() {
+				self.ctx().send_to(
+					pulse_actor_id(self.current_server),
+					PulseAudioAction::SetVolume(ident, vols),
+				);
+			}
 		}
 	}
 
@@ -304,19 +581,56 @@ impl RSState {
 			}
 		};
 
-		let percent = if let Some(play) = self.entries.get_play_entry(&ident) {
-			volume_to_percent(play.volume)
-		} else {
-			0
+		let vol = match self.entries.get_play_entry(&ident) {
+			Some(play) if play.expanded => play
+				.volume
+				.get()
+				.get(play.selected_channel)
+				.copied()
+				.unwrap_or(Volume::MUTED),
+			Some(play) => play.volume.avg(),
+			None => Volume::MUTED,
+		};
+		let value = match (*VARIABLES).get().volume_display {
+			VolumeDisplay::Db if vol == Volume::MUTED => "-infdB".to_string(),
+			VolumeDisplay::Db => format!("{:.1}dB", VolumeDB::from(vol).0),
+			_ => channel_volume_to_percent(vol).to_string(),
 		};
-		let percent = percent.to_string();
 
-		let cursor = percent.len();
+		let cursor = value.chars().count();
+
+		self.set_volume_input_value(value, cursor);
+	}
 
-		self.set_volume_input_value(percent, cursor as u8);
+	// the offset is edited in milliseconds
+	pub fn setup_latency_input(&mut self, ident: EntryIdentifier, port: usize) {
+		let offset = self
+			.entries
+			.get_card_entry(&ident)
+			.and_then(|card| card.ports.get(port))
+			.map_or(0, |p| p.latency_offset);
+		let value = format!("{:.1}", offset as f64 / 1000.0);
+		let cursor = value.chars().count();
+
+		self.set_volume_input_value(value, cursor);
 	}
 
-	pub fn set_volume_input_value(&mut self, percent: String, cursor: u8) {
+	pub fn confirm_latency_input(&mut self, ident: EntryIdentifier, port: usize) {
+		let port = unwrap_or_return!(self
+			.entries
+			.get_card_entry(&ident)
+			.and_then(|card| card.ports.get(port)))
+		.name
+		.clone();
+		let ms = unwrap_or_return!(self.input_exact_volume.value.parse::<f64>().ok());
+
+		self.ctx().send_to(
+			pulse_actor_id(ident.server),
+			PulseAudioAction::SetPortLatencyOffset(ident, port, (ms * 1000.0).round() as i64),
+		);
+	}
+
+	pub fn set_volume_input_value(&mut self, percent: String, cursor: usize) {
 		self.redraw.context_menu = true;
 
 		self.input_exact_volume.value = percent;
@@ -358,25 +672,58 @@ impl RSState {
 			}
 		};
 
-		let percent = match self.input_exact_volume.value.parse::<u16>() {
-			Ok(percent) => percent,
-			Err(_) => {
-				return;
-			}
-		};
-
-		let vol = percent_to_volume(percent as i16);
+		let input = self.input_exact_volume.value.clone();
 
 		if let Some(play) = self.entries.get_play_entry_mut(&selected) {
+			let max = max_volume(play);
 			let mut vols = play.volume;
 
-			for v in vols.get_mut() {
-				v.0 = vol;
+			if play.expanded {
+				if let Some(v) = vols.get_mut().get_mut(play.selected_channel) {
+					*v = unwrap_or_return!(parse_volume_input(&input, *v, max));
+				}
+			} else {
+				let vol = unwrap_or_return!(parse_volume_input(&input, vols.avg(), max));
+
+				for v in vols.get_mut() {
+					*v = vol;
+				}
 			}
 
-			self.ctx()
-				.send_to("pulseaudio", PulseAudioAction::SetVolume(selected, vols));
+			self.ctx().send_to(
+				pulse_actor_id(self.current_server),
+				PulseAudioAction::SetVolume(selected, vols),
+			);
+		}
+	}
+
+	pub fn setup_module_input(&mut self) {
+		self.input_text = TextInputWidget::default().title("Load module (name arguments)");
+	}
+
+	pub fn set_text_input_value(&mut self, value: String, cursor: usize) {
+		self.input_text.value = value;
+		self.input_text.cursor = cursor;
+
+		self.redraw.context_menu = true;
+	}
+
+	pub fn confirm_module_input(&mut self) {
+		let value = self.input_text.value.trim();
+
+		let (name, argument) = match value.split_once(char::is_whitespace) {
+			Some((name, argument)) => (name, argument.trim()),
+			None => (value, ""),
+		};
+
+		if name.is_empty() {
+			return;
 		}
+
+		self.ctx().send_to(
+			pulse_actor_id(self.current_server),
+			PulseAudioAction::LoadModule(name.to_string(), argument.to_string()),
+		);
 	}
 
 	pub fn confirm_context_menu(&mut self) {
@@ -393,15 +740,17 @@ impl RSState {
 			ContextMenuEffect::None => {
 				self.change_ui_mode(UIMode::Normal);
 			}
+			ContextMenuEffect::KeepOpen => {}
 			ContextMenuEffect::MoveEntry => {
 				let (parent_type, _) = self.current_page.parent_child_types();
 				let entry_ident = selected;
 
 				if let Some(parent_id) = self.entries.get_play_entry(&entry_ident).unwrap().parent {
-					let entry_parent = EntryIdentifier::new(parent_type, parent_id);
+					let entry_parent =
+						EntryIdentifier::new(parent_type, parent_id).with_server(selected.server);
 					let parent_ident = match self.entries.find(|(&i, _)| i == entry_parent) {
 						Some((i, _)) => *i,
-						None => EntryIdentifier::new(parent_type, 0),
+						None => EntryIdentifier::new(parent_type, 0).with_server(selected.server),
 					};
 
 					self.change_ui_mode(UIMode::MoveEntry(entry_ident, parent_ident));
@@ -411,9 +760,43 @@ impl RSState {
 					self.change_ui_mode(UIMode::Normal);
 				}
 			}
+			ContextMenuEffect::Details => {
+				self.show_details(selected);
+			}
+			ContextMenuEffect::LatencyOffset(port) => {
+				self.setup_latency_input(selected, port);
+				self.change_ui_mode(UIMode::InputLatencyOffset(selected, port));
+			}
+			ContextMenuEffect::PortsMenu => {
+				if let Some(entry) = self.entries.get(&selected) {
+					self.context_menu = ContextMenu::new_ports(entry);
+
+					if let EntryKind::PlayEntry(play) = &entry.entry_kind {
+						self.context_menu
+							.set_selected(play.active_port.unwrap_or(0));
+					}
+				}
+
+				self.redraw.resize = true;
+			}
 		};
 	}
 
+	pub fn cycle_profiles(&mut self, which_way: i8) {
+		let ident = unwrap_or_return!(self.page_entries.get_selected());
+		let card = unwrap_or_return!(self.entries.get_card_entry(&ident));
+		let profile = unwrap_or_return!(card.cycle_profile(which_way));
+
+		if card.selected_profile.map(|i| &card.profiles[i]) == Some(profile) {
+			return;
+		}
+
+		self.ctx().send_to(
+			pulse_actor_id(ident.server),
+			PulseAudioAction::ChangeCardProfile(ident, profile.name.clone()),
+		);
+	}
+
 	pub fn hide_entry(&mut self, ident: &Option<EntryIdentifier>) {
 		let ident = match *ident {
 			Some(i) => i,
@@ -445,3 +828,7 @@ impl RSState {
 			.insert(self.page_entries.selected());
 	}
 }
+
+fn max_volume(play: &PlayEntry) -> i16 {
+	(*VARIABLES).get().max_volume(play.device_name.as_deref()) as i16
+}
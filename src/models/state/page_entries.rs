@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use super::RSState;
 use crate::{
+	actors::pulse_actor_id,
 	entry::{EntryIdentifier, EntryKind, EntryType, HiddenStatus},
 	models::{PageType, PulseAudioAction, UIMode},
 	ui::Scrollable,
@@ -12,7 +13,7 @@ pub fn update(state: &mut RSState) {
 
 	let (p, c) = state.current_page.parent_child_types();
 
-	if p != EntryType::Card && c != EntryType::Card {
+	if state.current_page.has_play_entries() {
 		let mut parents = HashSet::new();
 		state.entries.iter_type(c).for_each(|(_, e)| {
 			if let EntryKind::PlayEntry(play) = &e.entry_kind {
@@ -30,6 +31,10 @@ pub fn update(state: &mut RSState) {
 		}
 	}
 
+	if state.current_page == PageType::Clients {
+		update_client_streams(state);
+	}
+
 	let entries_changed = state.page_entries.set(
 		state
 			.current_page
@@ -39,6 +44,23 @@ pub fn update(state: &mut RSState) {
 		p,
 	);
 
+	let channel_rows = state
+		.page_entries
+		.iter_entries()
+		.map(|ident| match state.entries.get_play_entry(ident) {
+			Some(play) => {
+				let channels = if play.expanded {
+					play.volume.len() as u16
+				} else {
+					0
+				};
+				channels + play.meter_rows() - 1
+			}
+			None => 0,
+		})
+		.collect();
+	let channel_rows_changed = state.page_entries.set_channel_rows(channel_rows);
+
 	match state.ui_mode {
 		UIMode::MoveEntry(ident, _) => {
 			if let Some(i) = state.page_entries.iter_entries().position(|&x| x == ident) {
@@ -58,26 +80,58 @@ pub fn update(state: &mut RSState) {
 
 	if entries_changed {
 		let monitors = monitor_list(state);
-		state
-			.ctx()
-			.send_to("pulseaudio", PulseAudioAction::CreateMonitors(monitors));
+		state.ctx().send_to(
+			pulse_actor_id(state.current_server),
+			PulseAudioAction::CreateMonitors(monitors),
+		);
 
 		state.redraw.resize = true;
 	}
+
+	if channel_rows_changed {
+		state.redraw.resize = true;
+	}
+}
+
+fn update_client_streams(state: &mut RSState) {
+	let mut streams: HashMap<u32, usize> = HashMap::new();
+
+	for entry_type in [EntryType::SinkInput, EntryType::SourceOutput] {
+		for (_, entry) in state.entries.iter_type(entry_type) {
+			if let Some(client) = entry.entry_kind.play_entry().and_then(|p| p.client) {
+				*streams.entry(client).or_insert(0) += 1;
+			}
+		}
+	}
+
+	for (_, entry) in state.entries.iter_type_mut(EntryType::Client) {
+		let count = *streams.get(&entry.index).unwrap_or(&0);
+
+		if let Some(client) = entry.entry_kind.client_entry_mut() {
+			if client.streams != count {
+				client.streams = count;
+				state.redraw.entries = true;
+			}
+		}
+	}
 }
 
-fn monitor_list(state: &mut RSState) -> HashMap<EntryIdentifier, Option<u32>> {
+fn monitor_list(state: &mut RSState) -> HashMap<EntryIdentifier, (Option<u32>, u8)> {
 	let mut monitors = HashMap::new();
 
-	if state.current_page == PageType::Cards {
+	if !state.current_page.has_play_entries() {
 		return monitors;
 	}
 
 	state.page_entries.iter_entries().for_each(|ident| {
 		if let Some(entry) = state.entries.get(ident) {
+			let channels = entry
+				.entry_kind
+				.play_entry()
+				.map_or(1, |play| play.monitor_channels());
 			monitors.insert(
-				EntryIdentifier::new(entry.entry_type, entry.index),
-				entry.monitor_source(&state.entries),
+				entry.entry_ident,
+				(entry.monitor_source(&state.entries), channels),
 			);
 		}
 	});
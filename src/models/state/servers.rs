@@ -0,0 +1,121 @@
+use std::{collections::HashMap, mem};
+
+use super::{page_entries, RSState};
+use crate::{
+	actors::pulse_actor_id,
+	entry::{Entries, Entry, EntryIdentifier},
+	models::{PulseAudioAction, ServerInfo, UIMode},
+	VARIABLES,
+};
+
+// entries and status of a server which isn't currently shown
+#[derive(Default)]
+pub struct ServerState {
+	pub entries: Entries,
+	pub server_info: ServerInfo,
+	pub retry_in: Option<u64>,
+}
+
+pub fn new_server_list() -> Vec<ServerState> {
+	(0..(*VARIABLES).get().pa_servers.len())
+		.map(|_| ServerState::default())
+		.collect()
+}
+
+pub fn server_label(server: usize) -> String {
+	match (*VARIABLES).get().pa_servers.get(server) {
+		Some(Some(address)) => address.clone(),
+		_ => "local".to_string(),
+	}
+}
+
+impl RSState {
+	pub fn cycle_servers(&mut self, which_way: i8) {
+		let count = self.servers.len() as i64;
+		if count < 2 {
+			return;
+		}
+
+		let next = (self.current_server as i64 + which_way as i64).rem_euclid(count);
+		self.switch_server(next as usize);
+	}
+
+	pub fn switch_server(&mut self, server: usize) {
+		if server == self.current_server || server >= self.servers.len() {
+			return;
+		}
+
+		// monitors are only kept for the shown server
+		self.ctx().send_to(
+			pulse_actor_id(self.current_server),
+			PulseAudioAction::CreateMonitors(HashMap::new()),
+		);
+
+		let old = &mut self.servers[self.current_server];
+		old.entries = mem::take(&mut self.entries);
+		old.server_info = mem::take(&mut self.server_info);
+		old.retry_in = match self.ui_mode {
+			UIMode::RetryIn(time) => Some(time),
+			_ => None,
+		};
+
+		let new = &mut self.servers[server];
+		self.entries = mem::take(&mut new.entries);
+		self.server_info = mem::take(&mut new.server_info);
+		let retry_in = new.retry_in.take();
+
+		self.current_server = server;
+		self.update_default_flags();
+
+		match retry_in {
+			Some(time) => self.change_ui_mode(UIMode::RetryIn(time)),
+			None => self.change_ui_mode(UIMode::Normal),
+		};
+
+		page_entries::update(self);
+	}
+
+	// updates of servers that aren't shown are only stored
+	pub(super) fn stash_entry(&mut self, ident: &EntryIdentifier, entry: Option<Entry>) {
+		if let Some(server) = self.servers.get_mut(ident.server) {
+			match entry {
+				Some(entry) => {
+					server.entries.insert(*ident, entry);
+				}
+				None => {
+					server.entries.remove(ident);
+				}
+			}
+		}
+	}
+
+	pub(super) fn stash_server_info(&mut self, server_info: ServerInfo) {
+		if let Some(server) = self.servers.get_mut(server_info.server) {
+			server.server_info = server_info;
+		}
+	}
+
+	pub fn server_disconnected(&mut self, server: usize) {
+		if server == self.current_server {
+			self.reset();
+		} else if let Some(s) = self.servers.get_mut(server) {
+			*s = ServerState::default();
+		}
+	}
+
+	pub fn server_retry_in(&mut self, server: usize, time: u64) {
+		if server == self.current_server {
+			self.change_ui_mode(UIMode::RetryIn(time));
+		} else if let Some(s) = self.servers.get_mut(server) {
+			s.retry_in = Some(time);
+		}
+	}
+
+	pub fn server_connected(&mut self, server: usize) {
+		if server == self.current_server {
+			self.change_ui_mode(UIMode::Normal);
+		} else if let Some(s) = self.servers.get_mut(server) {
+			s.retry_in = None;
+		}
+	}
+}
@@ -7,5 +7,10 @@ pub enum UIMode {
 	Help,
 	MoveEntry(EntryIdentifier, EntryIdentifier),
 	InputVolumeValue,
+	// card and index of its port
+	InputLatencyOffset(EntryIdentifier, usize),
+	InputModule,
+	ServerInfo,
+	Details(EntryIdentifier),
 	RetryIn(u64),
 }
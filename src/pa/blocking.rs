@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use super::{callbacks, common::*, pa_actions, pa_interface};
+use crate::{entry::Entry, models::ServerInfo};
+
+// how long to wait for PulseAudio to answer
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// everything PulseAudio knows about at one point in time
+pub struct Snapshot {
+	pub entries: HashMap<EntryIdentifier, Entry>,
+	pub server_info: Option<ServerInfo>,
+}
+
+// A connection for commands run outside of the TUI. Apart from `request_info`
+// every call waits until PulseAudio has answered, requests are processed in the
+// order they are sent.
+pub struct BlockingConnection {
+	mainloop: Rc<RefCell<Mainloop>>,
+	context: Rc<RefCell<PAContext>>,
+}
+
+impl BlockingConnection {
+	pub fn connect(server: Option<&str>) -> Result<Self> {
+		let (mainloop, context) = pa_interface::connect(server)?;
+
+		Ok(Self { mainloop, context })
+	}
+
+	pub fn snapshot(&self) -> Result<Snapshot> {
+		let (info_sx, mut info_rx) = mpsc::unbounded_channel();
+		let (actions_sx, mut actions_rx) = mpsc::unbounded_channel();
+
+		self.locked(|context| {
+			callbacks::request_current_state(Rc::clone(context), info_sx.clone(), &actions_sx)
+		})?;
+		self.sync()?;
+
+		// lists only name the entries, their info is requested one by one
+		let mut requested = Vec::new();
+		loop {
+			let mut idents = Vec::new();
+			while let Ok(ident) = info_rx.try_recv() {
+				if !requested.contains(&ident) {
+					requested.push(ident);
+					idents.push(ident);
+				}
+			}
+			if idents.is_empty() {
+				break;
+			}
+
+			self.locked(|context| {
+				for ident in idents {
+					callbacks::request_info(ident, context, info_sx.clone(), &actions_sx);
+				}
+			});
+			self.sync()?;
+		}
+
+		let mut snapshot = Snapshot {
+			entries: HashMap::new(),
+			server_info: None,
+		};
+		while let Ok(update) = actions_rx.try_recv() {
+			match update {
+				EntryUpdate::EntryUpdate(ident, entry) => {
+					snapshot.entries.insert(ident, *entry);
+				}
+				EntryUpdate::EntryRemoved(ident) => {
+					snapshot.entries.remove(&ident);
+				}
+				EntryUpdate::ServerInfo(info) => {
+					snapshot.server_info = Some(info);
+				}
+				EntryUpdate::PeakVolumeUpdates(_) => {}
+			}
+		}
+
+		if let Some(info) = &snapshot.server_info {
+			for entry in snapshot.entries.values_mut() {
+				let entry_type = entry.entry_type;
+				if let Some(play) = entry.entry_kind.play_entry_mut() {
+					play.is_default = info.is_default(entry_type, &play.device_name);
+				}
+			}
+		}
+
+		Ok(snapshot)
+	}
+
+	// from now on changes are sent to the channels like in the TUI, starting with
+	// the current state, so the receivers have to outlive the connection
+	pub fn subscribe(
+		&self,
+		info_sx: mpsc::UnboundedSender<EntryIdentifier>,
+		actions_sx: &ActionsSender,
+	) -> Result<()> {
+		self.locked(|context| {
+			callbacks::subscribe(context, info_sx.clone(), actions_sx.clone())?;
+			callbacks::request_current_state(Rc::clone(context), info_sx, actions_sx)
+		})?;
+
+		self.sync()
+	}
+
+	pub fn request_info(
+		&self,
+		ident: EntryIdentifier,
+		info_sx: mpsc::UnboundedSender<EntryIdentifier>,
+		actions_sx: &ActionsSender,
+	) {
+		self.locked(|context| callbacks::request_info(ident, context, info_sx, actions_sx));
+	}
+
+	pub fn is_ready(&self) -> bool {
+		self.locked(|context| context.borrow().get_state() == pulse::context::State::Ready)
+	}
+
+	pub fn run(&self, cmd: PulseAudioAction) -> Result<()> {
+		// callbacks unwrap their sends, so receivers live until the answer arrives
+		let (info_sx, _info_rx) = mpsc::unbounded_channel();
+		let (actions_sx, _actions_rx) = mpsc::unbounded_channel();
+
+		self.locked(|context| pa_actions::handle_command(cmd, context, &info_sx, &actions_sx));
+
+		self.sync()
+	}
+
+	fn locked<T>(&self, f: impl FnOnce(&Rc<RefCell<PAContext>>) -> T) -> T {
+		self.mainloop.borrow_mut().lock();
+		let res = f(&self.context);
+		self.mainloop.borrow_mut().unlock();
+
+		res
+	}
+
+	// answers come in order, so once this one arrives everything sent before is done
+	fn sync(&self) -> Result<()> {
+		let (sx, rx) = cb_channel::bounded(1);
+
+		self.locked(|context| {
+			context.borrow_mut().introspect().get_server_info(move |_| {
+				let _ = sx.send(());
+			});
+		});
+
+		match rx.recv_timeout(TIMEOUT) {
+			Ok(()) => Ok(()),
+			Err(_) => Err(PAError::PulseAudioDisconnected).context("while waiting for PulseAudio"),
+		}
+	}
+}
+
+impl Drop for BlockingConnection {
+	fn drop(&mut self) {
+		self.locked(|context| context.borrow_mut().disconnect());
+		self.mainloop.borrow_mut().stop();
+	}
+}
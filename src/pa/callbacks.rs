@@ -1,22 +1,32 @@
+use std::{borrow::Cow, cmp::Reverse, iter};
+
 use pulse::{
 	callbacks::ListResult,
+	channelmap::Map,
 	context::{
-		introspect::{CardInfo, SinkInfo, SinkInputInfo, SourceInfo, SourceOutputInfo},
+		introspect::{
+			CardInfo, ClientInfo, ModuleInfo, ServerInfo, SinkInfo, SinkInputInfo, SourceInfo,
+			SourceOutputInfo,
+		},
 		subscribe::{InterestMaskSet, Operation},
 	},
-	def::{SinkState, SourceState},
+	def::{PortAvailable, SinkState, SourceState},
+	proplist::Proplist,
+	sample::Spec,
+	time::MicroSeconds,
 };
 
-use super::{common::*, pa_interface::ACTIONS_SX};
+use super::common::*;
 use crate::{
-	entry::{CardProfile, Entry},
-	models::EntryUpdate,
+	entry::{CardPort, CardProfile, Entry, Port},
+	models::{self, EntryUpdate},
 	ui::Rect,
 };
 
 pub fn subscribe(
 	context: &Rc<RefCell<PAContext>>,
 	info_sx: mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: ActionsSender,
 ) -> Result<()> {
 	info!("[PAInterface] Registering pulseaudio callbacks");
 
@@ -27,22 +37,27 @@ pub fn subscribe(
 			| InterestMaskSet::CARD
 			| InterestMaskSet::SOURCE_OUTPUT
 			| InterestMaskSet::CLIENT
+			| InterestMaskSet::MODULE
 			| InterestMaskSet::SERVER,
 		|success: bool| {
 			assert!(success, "subscription failed");
 		},
 	);
 
+	let context_ref = Rc::downgrade(context);
 	context.borrow_mut().set_subscribe_callback(Some(Box::new(
 		move |facility, operation, index| {
 			if let Some(facility) = facility {
-				match facility {
-					Facility::Server | Facility::Client => {
-						log::error!("{facility:?} {operation:?}");
-						return;
+				if facility == Facility::Server {
+					info!("[PAInterface] Server changed");
+					if let Some(context) = context_ref.upgrade() {
+						context
+							.borrow()
+							.introspect()
+							.get_server_info(on_server_info(&actions_sx));
 					}
-					_ => {}
-				};
+					return;
+				}
 
 				let entry_type: EntryType = facility.into();
 				match operation {
@@ -61,8 +76,7 @@ pub fn subscribe(
 					}
 					Some(Operation::Removed) => {
 						info!("[PAInterface] {:?} removed", entry_type);
-						(*ACTIONS_SX)
-							.get()
+						actions_sx
 							.send(EntryUpdate::EntryRemoved(EntryIdentifier::new(
 								entry_type, index,
 							)))
@@ -80,11 +94,14 @@ pub fn subscribe(
 pub fn request_current_state(
 	context: Rc<RefCell<PAContext>>,
 	info_sxx: mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: &ActionsSender,
 ) -> Result<()> {
 	info!("[PAInterface] Requesting starting state");
 
 	let introspector = context.borrow_mut().introspect();
 
+	introspector.get_server_info(on_server_info(actions_sx));
+
 	let info_sx = info_sxx.clone();
 	introspector.get_sink_info_list(move |x: ListResult<&SinkInfo>| {
 		if let ListResult::Item(e) = x {
@@ -115,9 +132,23 @@ pub fn request_current_state(
 		}
 	});
 
+	let info_sx = info_sxx.clone();
 	introspector.get_card_info_list(move |x: ListResult<&CardInfo>| {
 		if let ListResult::Item(e) = x {
-			let _ = info_sxx.send(EntryIdentifier::new(EntryType::Card, e.index));
+			let _ = info_sx.send(EntryIdentifier::new(EntryType::Card, e.index));
+		}
+	});
+
+	let info_sx = info_sxx.clone();
+	introspector.get_module_info_list(move |x: ListResult<&ModuleInfo>| {
+		if let ListResult::Item(e) = x {
+			let _ = info_sx.send(EntryIdentifier::new(EntryType::Module, e.index));
+		}
+	});
+
+	introspector.get_client_info_list(move |x: ListResult<&ClientInfo>| {
+		if let ListResult::Item(e) = x {
+			let _ = info_sxx.send(EntryIdentifier::new(EntryType::Client, e.index));
 		}
 	});
 
@@ -128,6 +159,7 @@ pub fn request_info(
 	ident: EntryIdentifier,
 	context: &Rc<RefCell<PAContext>>,
 	info_sx: mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: &ActionsSender,
 ) {
 	let introspector = context.borrow_mut().introspect();
 	debug!(
@@ -136,71 +168,232 @@ pub fn request_info(
 	);
 	match ident.entry_type {
 		EntryType::SinkInput => {
-			introspector.get_sink_input_info(ident.index, on_sink_input_info(&info_sx));
+			introspector.get_sink_input_info(ident.index, on_sink_input_info(&info_sx, actions_sx));
 		}
 		EntryType::Sink => {
-			introspector.get_sink_info_by_index(ident.index, on_sink_info(&info_sx));
+			introspector.get_sink_info_by_index(ident.index, on_sink_info(&info_sx, actions_sx));
 		}
 		EntryType::SourceOutput => {
-			introspector.get_source_output_info(ident.index, on_source_output_info(&info_sx));
+			introspector
+				.get_source_output_info(ident.index, on_source_output_info(&info_sx, actions_sx));
 		}
 		EntryType::Source => {
-			introspector.get_source_info_by_index(ident.index, on_source_info(&info_sx));
+			introspector
+				.get_source_info_by_index(ident.index, on_source_info(&info_sx, actions_sx));
 		}
 		EntryType::Card => {
-			introspector.get_card_info_by_index(ident.index, on_card_info);
+			introspector.get_card_info_by_index(ident.index, on_card_info(actions_sx));
+		}
+		EntryType::Module => {
+			introspector.get_module_info(ident.index, on_module_info(actions_sx));
+		}
+		EntryType::Client => {
+			introspector.get_client_info(ident.index, on_client_info(actions_sx));
 		}
 	};
 }
-pub fn on_card_info(res: ListResult<&CardInfo>) {
-	if let ListResult::Item(i) = res {
-		let n = i
-			.proplist
-			.get_str(pulse::proplist::properties::DEVICE_DESCRIPTION)
-			.unwrap_or_default();
-		let profiles: Vec<CardProfile> = i
-			.profiles
-			.iter()
-			.filter_map(|p| {
-				p.name.clone().map(|n| CardProfile {
-					area: Rect::default(),
-					is_selected: false,
-					name: n.to_string(),
-					description: match &p.description {
-						Some(s) => s.to_string(),
-						None => n.to_string(),
-					},
-					#[cfg(feature = "pa_v13")]
-					available: p.available,
+pub fn on_server_info(actions_sx: &ActionsSender) -> impl Fn(&ServerInfo) {
+	let actions_sx = actions_sx.clone();
+	move |i: &ServerInfo| {
+		debug!("[PADataInterface] Update server info");
+		let server_info = models::ServerInfo {
+			default_sink: i.default_sink_name.as_ref().map(|n| n.to_string()),
+			default_source: i.default_source_name.as_ref().map(|n| n.to_string()),
+			server_name: i.server_name.as_ref().map(|n| n.to_string()),
+			server_version: i.server_version.as_ref().map(|n| n.to_string()),
+			host_name: i.host_name.as_ref().map(|n| n.to_string()),
+			user_name: i.user_name.as_ref().map(|n| n.to_string()),
+			sample_spec: Some(i.sample_spec.print()),
+			channel_map: Some(i.channel_map.print()),
+			..Default::default()
+		};
+
+		actions_sx
+			.send(EntryUpdate::ServerInfo(server_info))
+			.unwrap();
+	}
+}
+
+pub fn on_card_info(actions_sx: &ActionsSender) -> impl Fn(ListResult<&CardInfo>) {
+	let actions_sx = actions_sx.clone();
+	move |res: ListResult<&CardInfo>| {
+		if let ListResult::Item(i) = res {
+			let n = i
+				.proplist
+				.get_str(pulse::proplist::properties::DEVICE_DESCRIPTION)
+				.unwrap_or_default();
+			let mut profiles: Vec<CardProfile> = i
+				.profiles
+				.iter()
+				.filter_map(|p| {
+					p.name.clone().map(|n| CardProfile {
+						area: Rect::default(),
+						is_selected: false,
+						name: n.to_string(),
+						description: match &p.description {
+							Some(s) => s.to_string(),
+							None => n.to_string(),
+						},
+						#[cfg(feature = "pa_v13")]
+						available: p.available,
+						priority: p.priority,
+					})
 				})
-			})
-			.collect();
-
-		let selected_profile = match &i.active_profile {
-			Some(x) => {
-				if let Some(n) = &x.name {
-					profiles.iter().position(|p| p.name == *n)
-				} else {
-					None
+				.collect();
+			profiles.sort_by_key(|p| Reverse(p.priority));
+
+			let selected_profile = match &i.active_profile {
+				Some(x) => {
+					if let Some(n) = &x.name {
+						profiles.iter().position(|p| p.name == *n)
+					} else {
+						None
+					}
 				}
+				None => None,
+			};
+
+			let ports: Vec<CardPort> = i
+				.ports
+				.iter()
+				.filter_map(|p| {
+					p.name.clone().map(|n| CardPort {
+						name: n.to_string(),
+						description: match &p.description {
+							Some(s) => s.to_string(),
+							None => n.to_string(),
+						},
+						available: p.available != PortAvailable::No,
+						latency_offset: p.latency_offset,
+					})
+				})
+				.collect();
+
+			let ident = EntryIdentifier::new(EntryType::Card, i.index);
+			let entry =
+				Entry::new_card_entry(i.index, n, profiles, selected_profile).card_ports(ports);
+
+			actions_sx
+				.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
+				.unwrap();
+		}
+	}
+}
+
+pub fn on_module_info(actions_sx: &ActionsSender) -> impl Fn(ListResult<&ModuleInfo>) {
+	let actions_sx = actions_sx.clone();
+	move |res: ListResult<&ModuleInfo>| {
+		if let ListResult::Item(i) = res {
+			debug!("[PADataInterface] Update {} module info", i.index);
+			let name = match &i.name {
+				Some(name) => name.to_string(),
+				None => String::new(),
+			};
+			let argument = i
+				.argument
+				.as_ref()
+				.map(|a| a.to_string())
+				.filter(|a| !a.is_empty());
+
+			let ident = EntryIdentifier::new(EntryType::Module, i.index);
+			let entry = Entry::new_module_entry(i.index, name, argument, i.n_used);
+
+			actions_sx
+				.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
+				.unwrap();
+		}
+	}
+}
+
+pub fn on_client_info(actions_sx: &ActionsSender) -> impl Fn(ListResult<&ClientInfo>) {
+	let actions_sx = actions_sx.clone();
+	move |res: ListResult<&ClientInfo>| {
+		if let ListResult::Item(i) = res {
+			debug!("[PADataInterface] Update {} client info", i.index);
+			let name = match &i.name {
+				Some(name) => name.to_string(),
+				None => String::new(),
+			};
+			if name == "RsMixerContext" {
+				return;
 			}
-			None => None,
-		};
+			let binary = i
+				.proplist
+				.get_str(pulse::proplist::properties::APPLICATION_PROCESS_BINARY);
+			let pid = i
+				.proplist
+				.get_str(pulse::proplist::properties::APPLICATION_PROCESS_ID);
 
-		let ident = EntryIdentifier::new(EntryType::Card, i.index);
-		let entry = Entry::new_card_entry(i.index, n, profiles, selected_profile);
+			let ident = EntryIdentifier::new(EntryType::Client, i.index);
+			let entry = Entry::new_client_entry(i.index, name, binary, pid);
 
-		(*ACTIONS_SX)
-			.get()
-			.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
-			.unwrap();
+			actions_sx
+				.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
+				.unwrap();
+		}
 	}
 }
 
+fn new_port(
+	name: &Option<Cow<str>>,
+	description: &Option<Cow<str>>,
+	available: PortAvailable,
+) -> Option<Port> {
+	name.as_ref().map(|n| Port {
+		name: n.to_string(),
+		description: match description {
+			Some(d) => d.to_string(),
+			None => n.to_string(),
+		},
+		available: available != PortAvailable::No,
+	})
+}
+
+fn details(
+	sample_spec: &Spec,
+	channel_map: &Map,
+	driver: &Option<Cow<str>>,
+	owner_module: Option<u32>,
+	timing: &[(&str, MicroSeconds)],
+	proplist: &Proplist,
+) -> Vec<(String, String)> {
+	let mut lines = vec![
+		("Sample spec".to_string(), sample_spec.print()),
+		("Channel map".to_string(), channel_map.print()),
+		(
+			"Driver".to_string(),
+			driver.as_ref().map(|d| d.to_string()).unwrap_or_default(),
+		),
+		(
+			"Owner module".to_string(),
+			owner_module
+				.map(|m| m.to_string())
+				.unwrap_or_else(|| "-".to_string()),
+		),
+	];
+	for (label, usec) in timing {
+		lines.push((
+			label.to_string(),
+			format!("{:.2} ms", usec.0 as f64 / 1000.0),
+		));
+	}
+
+	let mut keys: Vec<String> = proplist.iter().collect();
+	keys.sort();
+	for key in keys {
+		let value = proplist.get_str(&key).unwrap_or_default();
+		lines.push((key, value));
+	}
+
+	lines
+}
+
 pub fn on_sink_info(
 	_sx: &mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: &ActionsSender,
 ) -> impl Fn(ListResult<&SinkInfo>) {
-	|res: ListResult<&SinkInfo>| {
+	let actions_sx = actions_sx.clone();
+	move |res: ListResult<&SinkInfo>| {
 		if let ListResult::Item(i) = res {
 			debug!("[PADataInterface] Update {} sink info", i.index);
 			let name = match &i.description {
@@ -208,6 +401,16 @@ pub fn on_sink_info(
 				None => String::new(),
 			};
 			let display_info = i.proplist.get_str("alsa.name").unwrap_or_default();
+			let ports: Vec<Port> = i
+				.ports
+				.iter()
+				.filter_map(|p| new_port(&p.name, &p.description, p.available))
+				.collect();
+			let active_port = i
+				.active_port
+				.as_ref()
+				.and_then(|x| x.name.as_ref())
+				.and_then(|n| ports.iter().position(|p| p.name == *n));
 			let ident = EntryIdentifier::new(EntryType::Sink, i.index);
 			let entry = Entry::new_play_entry(
 				EntryType::Sink,
@@ -219,10 +422,24 @@ pub fn on_sink_info(
 				Some(i.monitor_source),
 				None,
 				i.state == SinkState::Suspended,
-			);
+				i.name.as_ref().map(|n| n.to_string()),
+			)
+			.ports(ports, active_port)
+			.channel_map(i.channel_map)
+			.latency(i.latency.0, Some(i.configured_latency.0))
+			.details(details(
+				&i.sample_spec,
+				&i.channel_map,
+				&i.driver,
+				i.owner_module,
+				&[
+					("Latency", i.latency),
+					("Configured latency", i.configured_latency),
+				],
+				&i.proplist,
+			));
 
-			(*ACTIONS_SX)
-				.get()
+			actions_sx
 				.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
 				.unwrap();
 		}
@@ -231,7 +448,9 @@ pub fn on_sink_info(
 
 pub fn on_sink_input_info(
 	sx: &mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: &ActionsSender,
 ) -> impl Fn(ListResult<&SinkInputInfo>) {
+	let actions_sx = actions_sx.clone();
 	let info_sx = sx.clone();
 	move |res: ListResult<&SinkInputInfo>| {
 		if let ListResult::Item(i) = res {
@@ -258,10 +477,39 @@ pub fn on_sink_input_info(
 				None,
 				Some(i.sink),
 				false,
+				None,
+			)
+			.channel_map(i.channel_map)
+			.client(i.client)
+			.media(
+				i.proplist.get_str(pulse::proplist::properties::MEDIA_NAME),
+				i.proplist
+					.get_str(pulse::proplist::properties::APPLICATION_PROCESS_BINARY),
+			)
+			.latency(i.buffer_usec.0 + i.sink_usec.0, None)
+			.details(
+				iter::once((
+					"Resample method".to_string(),
+					i.resample_method
+						.as_ref()
+						.map(|r| r.to_string())
+						.unwrap_or_default(),
+				))
+				.chain(details(
+					&i.sample_spec,
+					&i.channel_map,
+					&i.driver,
+					i.owner_module,
+					&[
+						("Buffer latency", i.buffer_usec),
+						("Sink latency", i.sink_usec),
+					],
+					&i.proplist,
+				))
+				.collect(),
 			);
 
-			(*ACTIONS_SX)
-				.get()
+			actions_sx
 				.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
 				.unwrap();
 			let _ = info_sx.send(EntryIdentifier::new(EntryType::Sink, i.sink));
@@ -271,7 +519,9 @@ pub fn on_sink_input_info(
 
 pub fn on_source_info(
 	_sx: &mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: &ActionsSender,
 ) -> impl Fn(ListResult<&SourceInfo>) {
+	let actions_sx = actions_sx.clone();
 	move |res: ListResult<&SourceInfo>| {
 		if let ListResult::Item(i) = res {
 			debug!("[PADataInterface] Update {} source info", i.index);
@@ -279,6 +529,16 @@ pub fn on_source_info(
 				Some(name) => name.to_string(),
 				None => String::new(),
 			};
+			let ports: Vec<Port> = i
+				.ports
+				.iter()
+				.filter_map(|p| new_port(&p.name, &p.description, p.available))
+				.collect();
+			let active_port = i
+				.active_port
+				.as_ref()
+				.and_then(|x| x.name.as_ref())
+				.and_then(|n| ports.iter().position(|p| p.name == *n));
 			let ident = EntryIdentifier::new(EntryType::Source, i.index);
 			let entry = Entry::new_play_entry(
 				EntryType::Source,
@@ -290,10 +550,24 @@ pub fn on_source_info(
 				Some(i.index),
 				None,
 				i.state == SourceState::Suspended,
-			);
+				i.name.as_ref().map(|n| n.to_string()),
+			)
+			.ports(ports, active_port)
+			.channel_map(i.channel_map)
+			.latency(i.latency.0, Some(i.configured_latency.0))
+			.details(details(
+				&i.sample_spec,
+				&i.channel_map,
+				&i.driver,
+				i.owner_module,
+				&[
+					("Latency", i.latency),
+					("Configured latency", i.configured_latency),
+				],
+				&i.proplist,
+			));
 
-			(*ACTIONS_SX)
-				.get()
+			actions_sx
 				.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
 				.unwrap();
 		}
@@ -302,7 +576,9 @@ pub fn on_source_info(
 
 pub fn on_source_output_info(
 	sx: &mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: &ActionsSender,
 ) -> impl Fn(ListResult<&SourceOutputInfo>) {
+	let actions_sx = actions_sx.clone();
 	let info_sx = sx.clone();
 	move |res: ListResult<&SourceOutputInfo>| {
 		if let ListResult::Item(i) = res {
@@ -325,10 +601,40 @@ pub fn on_source_output_info(
 				Some(i.source),
 				None,
 				false,
+				None,
+			)
+			.corked(i.corked)
+			.channel_map(i.channel_map)
+			.client(i.client)
+			.media(
+				i.proplist.get_str(pulse::proplist::properties::MEDIA_NAME),
+				i.proplist
+					.get_str(pulse::proplist::properties::APPLICATION_PROCESS_BINARY),
+			)
+			.latency(i.buffer_usec.0 + i.source_usec.0, None)
+			.details(
+				iter::once((
+					"Resample method".to_string(),
+					i.resample_method
+						.as_ref()
+						.map(|r| r.to_string())
+						.unwrap_or_default(),
+				))
+				.chain(details(
+					&i.sample_spec,
+					&i.channel_map,
+					&i.driver,
+					i.owner_module,
+					&[
+						("Buffer latency", i.buffer_usec),
+						("Source latency", i.source_usec),
+					],
+					&i.proplist,
+				))
+				.collect(),
 			);
 
-			(*ACTIONS_SX)
-				.get()
+			actions_sx
 				.send(EntryUpdate::EntryUpdate(ident, Box::new(entry)))
 				.unwrap();
 			let _ = info_sx.send(EntryIdentifier::new(EntryType::Source, i.index));
@@ -7,7 +7,7 @@ pub use pulse::{
 };
 pub use tokio::sync::mpsc;
 
-pub use super::{errors::PAError, monitor::Monitors, PAInternal};
+pub use super::{errors::PAError, PAInternal};
 pub use crate::{
 	entry::{EntryIdentifier, EntryType},
 	models::{EntryUpdate, PulseAudioAction},
@@ -16,6 +16,11 @@ pub use crate::{
 
 pub static LOGGING_MODULE: &str = "PAInterface";
 
+pub type ActionsSender = mpsc::UnboundedSender<EntryUpdate>;
+
+// monitor source and channel count of each monitored entry
+pub type MonitorTargets = HashMap<EntryIdentifier, (Option<u32>, u8)>;
+
 impl From<Facility> for EntryType {
 	fn from(fac: Facility) -> Self {
 		match fac {
@@ -24,6 +29,8 @@ impl From<Facility> for EntryType {
 			Facility::SinkInput => EntryType::SinkInput,
 			Facility::SourceOutput => EntryType::SourceOutput,
 			Facility::Card => EntryType::Card,
+			Facility::Module => EntryType::Module,
+			Facility::Client => EntryType::Client,
 			_ => EntryType::Sink,
 		}
 	}
@@ -1,3 +1,4 @@
+mod blocking;
 mod callbacks;
 pub mod common;
 mod errors;
@@ -5,13 +6,14 @@ mod monitor;
 mod pa_actions;
 mod pa_interface;
 
+pub use blocking::{BlockingConnection, Snapshot};
 use common::*;
 use lazy_static::lazy_static;
+pub use monitor::start_monitors;
 pub use pa_interface::start;
 
 #[derive(Debug)]
 pub enum PAInternal {
-	Tick,
 	Command(Box<PulseAudioAction>),
 	AskInfo(EntryIdentifier),
 }
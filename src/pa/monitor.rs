@@ -1,129 +1,316 @@
-use std::convert::TryInto;
+use std::{
+	convert::TryInto,
+	ops::Deref,
+	time::{Duration, Instant},
+};
 
-use pulse::stream::PeekResult;
+use pulse::{
+	mainloop::standard::Mainloop as StandardMainloop, proplist::Proplist, stream::PeekResult,
+	time::MicroSeconds,
+};
 
-use super::{common::*, pa_interface::ACTIONS_SX};
-use crate::VARIABLES;
+use super::common::*;
+use crate::{config::MeterMode, entry::MeterLevel, VARIABLES};
+
+// RMS is measured on real samples, PulseAudio's peak detection would only hand
+// over the peak of each fragment
+const RMS_RATE: u32 = 48000;
+
+// how often levels are sent to the UI, independent of the monitor sample rate
+const BATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+// levels read since the last batch was sent
+type Levels = Rc<RefCell<HashMap<EntryIdentifier, Vec<MeterLevel>>>>;
 
 pub struct Monitor {
 	stream: Rc<RefCell<Stream>>,
-	exit_sender: cb_channel::Sender<u32>,
+	channels: u8,
 }
 
-#[derive(Default)]
 pub struct Monitors {
 	monitors: HashMap<EntryIdentifier, Monitor>,
 	errors: HashMap<EntryIdentifier, usize>,
+	actions_sx: ActionsSender,
+	levels: Levels,
+}
+
+// Meters get their own connection on their own thread, so commands are never
+// queued behind meter callbacks and failing monitors can't take the control
+// connection down. Reconnects until `targets_rx` is disconnected.
+pub fn start_monitors(
+	targets_rx: cb_channel::Receiver<MonitorTargets>,
+	actions_sx: ActionsSender,
+	server: Option<String>,
+) {
+	let mut targets = HashMap::new();
+
+	loop {
+		match run(&targets_rx, &mut targets, &actions_sx, server.as_deref()) {
+			Ok(()) => return,
+			Err(err) => {
+				warn!("[PAMonitors] {:?}", err);
+			}
+		}
+
+		let retry_time = Duration::from_secs((*VARIABLES).get().pa_retry_time);
+		let retry_at = Instant::now() + retry_time;
+		loop {
+			match targets_rx.recv_timeout(retry_at.saturating_duration_since(Instant::now())) {
+				Ok(t) => targets = t,
+				Err(cb_channel::RecvTimeoutError::Timeout) => break,
+				Err(cb_channel::RecvTimeoutError::Disconnected) => return,
+			}
+		}
+	}
+}
+
+fn run(
+	targets_rx: &cb_channel::Receiver<MonitorTargets>,
+	targets: &mut MonitorTargets,
+	actions_sx: &ActionsSender,
+	server: Option<&str>,
+) -> Result<()> {
+	let mut proplist = Proplist::new().unwrap();
+	proplist
+		.set_str(pulse::proplist::properties::APPLICATION_NAME, "RsMixer")
+		.unwrap();
+
+	debug!("[PAMonitors] Creating new mainloop");
+	let mainloop = Rc::new(RefCell::new(match StandardMainloop::new() {
+		Some(ml) => ml,
+		None => return Err(PAError::MainloopCreateError.into()),
+	}));
+
+	// same name as the control context, so our own streams stay hidden
+	let context = Rc::new(RefCell::new(
+		match PAContext::new_with_proplist(mainloop.borrow().deref(), "RsMixerContext", &proplist) {
+			Some(ctx) => ctx,
+			None => return Err(PAError::MainloopCreateError.into()),
+		},
+	));
+
+	if context
+		.borrow_mut()
+		.connect(server, pulse::context::FlagSet::NOFLAGS, None)
+		.is_err()
+	{
+		return Err(PAError::MainloopConnectError.into());
+	}
+
+	debug!("[PAMonitors] Waiting for context to be ready...");
+	loop {
+		if mainloop.borrow_mut().iterate(true).is_error() {
+			return Err(PAError::MainloopConnectError.into());
+		}
+		match context.borrow().get_state() {
+			pulse::context::State::Ready => break,
+			pulse::context::State::Failed | pulse::context::State::Terminated => {
+				return Err(PAError::MainloopConnectError.into());
+			}
+			_ => {}
+		}
+	}
+
+	let mut monitors = Monitors::new(actions_sx.clone());
+	monitors.filter(&context, targets);
+
+	let mut last_batch = Instant::now();
+
+	let res = loop {
+		let timeout = BATCH_INTERVAL.saturating_sub(last_batch.elapsed());
+		{
+			let mut mainloop = mainloop.borrow_mut();
+			if mainloop
+				.prepare(Some(MicroSeconds(timeout.as_micros() as u64)))
+				.and_then(|_| mainloop.poll())
+				.and_then(|_| mainloop.dispatch())
+				.is_err()
+			{
+				break Err(PAError::PulseAudioDisconnected.into());
+			}
+		}
+
+		if context.borrow().get_state() != pulse::context::State::Ready {
+			break Err(PAError::PulseAudioDisconnected.into());
+		}
+
+		let mut changed = false;
+		let mut finished = false;
+		loop {
+			match targets_rx.try_recv() {
+				Ok(t) => {
+					*targets = t;
+					changed = true;
+				}
+				Err(cb_channel::TryRecvError::Empty) => break,
+				Err(cb_channel::TryRecvError::Disconnected) => {
+					finished = true;
+					break;
+				}
+			}
+		}
+
+		if finished {
+			break Ok(());
+		}
+
+		if changed {
+			monitors.filter(&context, targets);
+		}
+
+		if last_batch.elapsed() >= BATCH_INTERVAL {
+			last_batch = Instant::now();
+
+			// also recreates failed monitors
+			monitors.filter(&context, targets);
+			if !monitors.flush() {
+				break Ok(());
+			}
+		}
+	};
+
+	monitors.filter(&context, &HashMap::new());
+	context.borrow_mut().disconnect();
+
+	res
 }
 
 impl Monitors {
-	pub fn filter(
-		&mut self,
-		mainloop: &Rc<RefCell<Mainloop>>,
-		context: &Rc<RefCell<PAContext>>,
-		targets: &HashMap<EntryIdentifier, Option<u32>>,
-	) {
-		// remove failed streams
-		// then send exit signal if stream is unwanted
+	pub fn new(actions_sx: ActionsSender) -> Self {
+		Self {
+			monitors: HashMap::new(),
+			errors: HashMap::new(),
+			actions_sx,
+			levels: Rc::new(RefCell::new(HashMap::new())),
+		}
+	}
+
+	pub fn filter(&mut self, context: &Rc<RefCell<PAContext>>, targets: &MonitorTargets) {
+		let errors = &mut self.errors;
+
+		// remove failed streams, then disconnect unwanted ones
 		self.monitors.retain(|ident, monitor| {
-			match monitor.stream.borrow_mut().get_state() {
+			let mut stream = monitor.stream.borrow_mut();
+
+			match stream.get_state() {
 				pulse::stream::State::Terminated | pulse::stream::State::Failed => {
 					info!(
-						"[PAInterface] Disconnecting {} sink input monitor (failed state)",
+						"[PAMonitors] Disconnecting {} monitor (failed state)",
 						ident.index
 					);
+					*errors.entry(*ident).or_insert(0) += 1;
 					return false;
 				}
+				pulse::stream::State::Ready => {
+					errors.remove(ident);
+				}
 				_ => {}
 			};
 
-			if targets.get(ident).is_none() {
-				let _ = monitor.exit_sender.send(0);
+			match targets.get(ident) {
+				Some((_, channels)) if *channels == monitor.channels => true,
+				// gone or the channel count changed, in that case it's recreated below
+				_ => {
+					let _ = stream.disconnect();
+					false
+				}
 			}
-
-			true
 		});
 
-		targets.iter().for_each(|(ident, monitor_src)| {
+		targets.iter().for_each(|(ident, (monitor_src, channels))| {
 			if !self.monitors.contains_key(ident) {
-				self.create_monitor(mainloop, context, *ident, *monitor_src);
+				self.create_monitor(context, *ident, *monitor_src, *channels);
 			}
 		});
 	}
 
+	// sends levels gathered since the last call, `false` if nobody listens anymore
+	pub fn flush(&mut self) -> bool {
+		let levels: Vec<(EntryIdentifier, Vec<MeterLevel>)> =
+			self.levels.borrow_mut().drain().collect();
+
+		levels.is_empty()
+			|| self
+				.actions_sx
+				.send(EntryUpdate::PeakVolumeUpdates(levels))
+				.is_ok()
+	}
+
 	fn create_monitor(
 		&mut self,
-		mainloop: &Rc<RefCell<Mainloop>>,
 		context: &Rc<RefCell<PAContext>>,
 		ident: EntryIdentifier,
 		monitor_src: Option<u32>,
+		channels: u8,
 	) {
 		if let Some(count) = self.errors.get(&ident) {
 			if *count >= 5 {
 				self.errors.remove(&ident);
-				(*ACTIONS_SX)
-					.get()
-					.send(EntryUpdate::EntryRemoved(ident))
-					.unwrap();
+				let _ = self.actions_sx.send(EntryUpdate::EntryRemoved(ident));
 			}
 		}
-		if self.monitors.contains_key(&ident) {
-			return;
-		}
-		let (sx, rx) = cb_channel::unbounded();
-		if let Ok(stream) = create(
-			mainloop,
+
+		match create(
 			context,
 			&pulse::sample::Spec {
 				format: pulse::sample::Format::FLOAT32NE,
-				channels: 1,
-				rate: (*VARIABLES).get().pa_rate,
+				channels: channels.max(1),
+				rate: match (*VARIABLES).get().meter.mode {
+					MeterMode::Peak => (*VARIABLES).get().pa_rate,
+					MeterMode::Rms => RMS_RATE,
+				},
 			},
 			ident,
 			monitor_src,
-			rx,
+			Rc::clone(&self.levels),
 		) {
-			self.monitors.insert(
-				ident,
-				Monitor {
-					stream,
-					exit_sender: sx,
-				},
-			);
-			self.errors.remove(&ident);
-		} else {
-			self.error(&ident);
+			Ok(stream) => {
+				self.monitors.insert(ident, Monitor { stream, channels });
+			}
+			Err(err) => {
+				warn!("[PAMonitors] {:?}", err);
+				*self.errors.entry(ident).or_insert(0) += 1;
+			}
 		}
 	}
-
-	fn error(&mut self, ident: &EntryIdentifier) {
-		let count = match self.errors.get(ident) {
-			Some(x) => *x,
-			None => 0,
-		};
-		self.errors.insert(*ident, count + 1);
-	}
 }
 
 fn slice_to_4_bytes(slice: &[u8]) -> [u8; 4] {
 	slice.try_into().expect("slice with incorrect length")
 }
 
+// samples are interleaved, one frame holds a sample of each channel
+fn measure(data: &[u8], channels: usize) -> Vec<MeterLevel> {
+	let count = data.len() / 4;
+	let frames = (count / channels).max(1);
+	let mut peaks = vec![0f32; channels];
+	let mut sum_squares = vec![0f32; channels];
+
+	for c in 0..count {
+		let sample = f32::from_ne_bytes(slice_to_4_bytes(&data[c * 4..(c + 1) * 4]));
+		let channel = c % channels;
+		peaks[channel] = peaks[channel].max(sample.abs());
+		sum_squares[channel] += sample * sample;
+	}
+
+	peaks
+		.iter()
+		.zip(&sum_squares)
+		.map(|(peak, sum)| MeterLevel {
+			peak: *peak,
+			rms: (sum / frames as f32).sqrt(),
+		})
+		.collect()
+}
+
 fn create(
-	p_mainloop: &Rc<RefCell<Mainloop>>,
 	p_context: &Rc<RefCell<PAContext>>,
 	p_spec: &pulse::sample::Spec,
 	ident: EntryIdentifier,
 	source_index: Option<u32>,
-	close_rx: cb_channel::Receiver<u32>,
+	levels: Levels,
 ) -> Result<Rc<RefCell<Stream>>> {
-	info!("[PADataInterface] Attempting to create new monitor stream");
-
-	let stream_index = if ident.entry_type == EntryType::SinkInput {
-		Some(ident.index)
-	} else {
-		None
-	};
+	info!("[PAMonitors] Attempting to create new monitor stream");
 
 	let stream = Rc::new(RefCell::new(
 		match Stream::new(&mut p_context.borrow_mut(), "RsMixer monitor", p_spec, None) {
@@ -135,136 +322,86 @@ fn create(
 		},
 	));
 
-	// Stream state change callback
+	// for sink inputs we want to set monitor stream to sink
+	if ident.entry_type == EntryType::SinkInput
+		&& stream.borrow_mut().set_monitor_stream(ident.index).is_err()
 	{
-		debug!("[PADataInterface] Registering stream state change callback");
-		let ml_ref = Rc::clone(p_mainloop);
-		let stream_ref = Rc::downgrade(&stream);
-		stream
-			.borrow_mut()
-			.set_state_callback(Some(Box::new(move || {
-				let state = unsafe { (*(*stream_ref.as_ptr()).as_ptr()).get_state() };
-				match state {
-					pulse::stream::State::Ready
-					| pulse::stream::State::Failed
-					| pulse::stream::State::Terminated => {
-						unsafe { (*ml_ref.as_ptr()).signal(false) };
-					}
-					_ => {}
-				}
-			})));
+		return Err(PAError::StreamCreateError).context("while setting the sink input to monitor");
 	}
 
-	// for sink inputs we want to set monitor stream to sink
-	if let Some(index) = stream_index {
-		stream.borrow_mut().set_monitor_stream(index).unwrap();
-	}
+	let source = source_index.map(|i| i.to_string());
 
-	let x;
-	let mut s = None;
-	if let Some(i) = source_index {
-		x = i.to_string();
-		s = Some(x.as_str());
-	}
-
-	debug!("[PADataInterface] Connecting stream");
-	match stream.borrow_mut().connect_record(
-		s,
-		Some(&pulse::def::BufferAttr {
-			maxlength: u32::MAX,
-			tlength: u32::MAX,
-			prebuf: u32::MAX,
-			minreq: 0,
-			fragsize: (*VARIABLES).get().pa_frag_size,
-		}),
-		pulse::stream::FlagSet::PEAK_DETECT | pulse::stream::FlagSet::ADJUST_LATENCY,
-	) {
-		Ok(_) => {}
-		Err(_) => {
-			return Err(PAError::StreamCreateError)
-				.context("while connecting stream for monitoring volume");
-		}
+	let (flags, fragsize) = match (*VARIABLES).get().meter.mode {
+		MeterMode::Peak => (
+			pulse::stream::FlagSet::PEAK_DETECT | pulse::stream::FlagSet::ADJUST_LATENCY,
+			(*VARIABLES).get().pa_frag_size,
+		),
+		// a fragment lasts as long as one sample of peak detection would
+		MeterMode::Rms => (
+			pulse::stream::FlagSet::ADJUST_LATENCY,
+			(p_spec.bytes_per_second() / (*VARIABLES).get().pa_rate.max(1) as usize) as u32,
+		),
 	};
 
-	debug!("[PADataInterface] Waiting for stream to be ready");
-	loop {
-		match stream.borrow_mut().get_state() {
-			pulse::stream::State::Ready => {
-				break;
-			}
-			pulse::stream::State::Failed | pulse::stream::State::Terminated => {
-				error!("[PADataInterface] Stream state failed/terminated");
-				return Err(PAError::StreamCreateError).context("stream terminated");
-			}
-			_ => {
-				p_mainloop.borrow_mut().wait();
-			}
-		}
+	debug!("[PAMonitors] Connecting stream");
+	if stream
+		.borrow_mut()
+		.connect_record(
+			source.as_deref(),
+			Some(&pulse::def::BufferAttr {
+				maxlength: u32::MAX,
+				tlength: u32::MAX,
+				prebuf: u32::MAX,
+				minreq: 0,
+				fragsize,
+			}),
+			flags,
+		)
+		.is_err()
+	{
+		return Err(PAError::StreamCreateError)
+			.context("while connecting stream for monitoring volume");
 	}
 
-	stream.borrow_mut().set_state_callback(None);
+	// callbacks run on this thread while the mainloop is dispatching, when no stream is borrowed
+	let channels = (p_spec.channels as usize).max(1);
+	let stream_ref = Rc::downgrade(&stream);
+	stream
+		.borrow_mut()
+		.set_read_callback(Some(Box::new(move |_size: usize| {
+			let stream = match stream_ref.upgrade() {
+				Some(stream) => stream,
+				None => return,
+			};
+			let mut stream = stream.borrow_mut();
 
-	{
-		info!("[PADataInterface] Registering stream read callback");
-		let ml_ref = Rc::clone(p_mainloop);
-		let stream_ref = Rc::downgrade(&stream);
-		stream.borrow_mut().set_read_callback(Some(Box::new(move |_size: usize| {
-            let remove_failed = || {
-                error!("[PADataInterface] Monitor failed or terminated");
-            };
-            let disconnect_stream = || {
-                warn!("[PADataInterface] {:?} Monitor existed while the sink (input)/source (output) was already gone", ident);
-                unsafe {
-                    (*(*stream_ref.as_ptr()).as_ptr()).disconnect().unwrap();
-                    (*ml_ref.as_ptr()).signal(false);
-                };
-            };
-
-            if close_rx.try_recv().is_ok() {
-                disconnect_stream();
-                return;
-            }
-
-            match unsafe {(*(*stream_ref.as_ptr()).as_ptr()).get_state() }{
-                pulse::stream::State::Failed => {
-                    remove_failed();
-                },
-                pulse::stream::State::Terminated => {
-                    remove_failed();
-                },
-                pulse::stream::State::Ready => {
-                    match unsafe{ (*(*stream_ref.as_ptr()).as_ptr()).peek() } {
-                        Ok(res) => match res {
-                            PeekResult::Data(data) => {
-                                let count = data.len() / 4;
-                                let mut peak = 0.0;
-                                for c in 0..count {
-                                    let data_slice = slice_to_4_bytes(&data[c * 4 .. (c + 1) * 4]);
-                                    peak += f32::from_ne_bytes(data_slice).abs();
-                                }
-                                peak /=  count as f32;
-
-                                if (*ACTIONS_SX).get().send(EntryUpdate::PeakVolumeUpdate(ident, peak)).is_err() {
-                                    disconnect_stream();
-                                }
-
-                                unsafe { (*(*stream_ref.as_ptr()).as_ptr()).discard().unwrap(); };
-                            },
-                            PeekResult::Hole(_) => {
-                                unsafe { (*(*stream_ref.as_ptr()).as_ptr()).discard().unwrap(); };
-                            },
-                            _ => {},
-                        },
-                        Err(_) => {
-                            remove_failed();
-                        },
-                    }
-                },
-                _ => {},
-            };
-            // unsafe {(*ml_ref.get_mut().unwrap()).signal(false)};
-        })));
-	}
+			match stream.peek() {
+				Ok(PeekResult::Data(data)) => {
+					let measured = measure(data, channels);
+
+					// keep the loudest fragment of each batch
+					levels
+						.borrow_mut()
+						.entry(ident)
+						.and_modify(|old| {
+							for (old, new) in old.iter_mut().zip(&measured) {
+								old.peak = old.peak.max(new.peak);
+								old.rms = old.rms.max(new.rms);
+							}
+						})
+						.or_insert(measured);
+
+					let _ = stream.discard();
+				}
+				Ok(PeekResult::Hole(_)) => {
+					let _ = stream.discard();
+				}
+				Ok(PeekResult::Empty) => {}
+				Err(_) => {
+					error!("[PAMonitors] Monitor failed or terminated");
+				}
+			}
+		})));
 
 	Ok(stream)
 }
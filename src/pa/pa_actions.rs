@@ -4,10 +4,12 @@ pub fn handle_command(
 	cmd: PulseAudioAction,
 	context: &Rc<RefCell<PAContext>>,
 	info_sx: &mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: &ActionsSender,
 ) -> Option<()> {
 	match cmd {
 		PulseAudioAction::RequestPulseAudioState => {
-			callbacks::request_current_state(Rc::clone(context), info_sx.clone()).unwrap();
+			callbacks::request_current_state(Rc::clone(context), info_sx.clone(), actions_sx)
+				.unwrap();
 		}
 		PulseAudioAction::MuteEntry(ident, mute) => {
 			set_mute(ident, mute, context);
@@ -27,8 +29,36 @@ pub fn handle_command(
 		PulseAudioAction::KillEntry(ident) => {
 			kill_entry(ident, context);
 		}
+		PulseAudioAction::SetDefaultSink(name) => {
+			context.borrow_mut().set_default_sink(&name, |_| {});
+		}
+		PulseAudioAction::SetDefaultSource(name) => {
+			context.borrow_mut().set_default_source(&name, |_| {});
+		}
+		PulseAudioAction::SetPort(ident, port) => {
+			set_port(ident, port, context);
+		}
+		PulseAudioAction::SetPortLatencyOffset(ident, port, offset) => {
+			set_port_latency_offset(ident, port, offset, context);
+		}
+		PulseAudioAction::LoadModule(name, argument) => {
+			let module = name.clone();
+			context
+				.borrow_mut()
+				.introspect()
+				.load_module(&name, &argument, move |index| {
+					if index == pulse::def::INVALID_INDEX {
+						warn!("Failed to load module {}", module);
+					}
+				});
+		}
+		PulseAudioAction::UnloadModule(ident) => {
+			unload_module(ident, context);
+		}
+		PulseAudioAction::KillClient(index) => {
+			context.borrow_mut().introspect().kill_client(index, |_| {});
+		}
 		PulseAudioAction::Shutdown => {
-			//@TODO disconnect monitors
 			return None;
 		}
 		_ => {}
@@ -69,6 +99,47 @@ fn change_card_profile(ident: EntryIdentifier, profile: String, context: &Rc<Ref
 		.set_card_profile_by_index(ident.index, &profile[..], None);
 }
 
+fn set_port_latency_offset(
+	ident: EntryIdentifier,
+	port: String,
+	offset: i64,
+	context: &Rc<RefCell<PAContext>>,
+) {
+	if ident.entry_type != EntryType::Card {
+		return;
+	}
+	// cards can be looked up by their index instead of the name
+	context.borrow_mut().introspect().set_port_latency_offset(
+		&ident.index.to_string(),
+		&port[..],
+		offset,
+		None,
+	);
+}
+
+fn set_port(ident: EntryIdentifier, port: String, context: &Rc<RefCell<PAContext>>) {
+	let mut introspector = context.borrow_mut().introspect();
+	match ident.entry_type {
+		EntryType::Sink => {
+			introspector.set_sink_port_by_index(ident.index, &port[..], None);
+		}
+		EntryType::Source => {
+			introspector.set_source_port_by_index(ident.index, &port[..], None);
+		}
+		_ => {}
+	};
+}
+
+fn unload_module(ident: EntryIdentifier, context: &Rc<RefCell<PAContext>>) {
+	if ident.entry_type != EntryType::Module {
+		return;
+	}
+	context
+		.borrow_mut()
+		.introspect()
+		.unload_module(ident.index, |_| {});
+}
+
 fn move_entry_to_parent(
 	ident: EntryIdentifier,
 	parent: EntryIdentifier,
@@ -1,22 +1,13 @@
 use std::ops::Deref;
 
-use lazy_static::lazy_static;
 use pulse::proplist::Proplist;
-use state::InitCell;
 
 use super::{callbacks, common::*, pa_actions};
 
-lazy_static! {
-	pub static ref ACTIONS_SX: InitCell<mpsc::UnboundedSender<EntryUpdate>> = InitCell::new();
-}
-
-pub fn start(
-	internal_rx: cb_channel::Receiver<PAInternal>,
-	info_sx: mpsc::UnboundedSender<EntryIdentifier>,
-	actions_sx: mpsc::UnboundedSender<EntryUpdate>,
-) -> Result<()> {
-	(*ACTIONS_SX).set(actions_sx);
+pub type Connection = (Rc<RefCell<Mainloop>>, Rc<RefCell<PAContext>>);
 
+// starts a mainloop with a ready context, the mainloop is left unlocked
+pub fn connect(server: Option<&str>) -> Result<Connection> {
 	// Create new mainloop and context
 	let mut proplist = Proplist::new().unwrap();
 	proplist
@@ -68,11 +59,14 @@ pub fn start(
 	}
 
 	// Try to connect to pulseaudio
-	debug!("[PAInterface] Connecting context");
+	debug!(
+		"[PAInterface] Connecting context to {}",
+		server.unwrap_or("default server")
+	);
 
 	if context
 		.borrow_mut()
-		.connect(None, pulse::context::FlagSet::NOFLAGS, None)
+		.connect(server, pulse::context::FlagSet::NOFLAGS, None)
 		.is_err()
 	{
 		error!("[PAInterface] Error while connecting context");
@@ -108,17 +102,28 @@ pub fn start(
 	debug!("[PAInterface] PAContext ready");
 
 	context.borrow_mut().set_state_callback(None);
+	mainloop.borrow_mut().unlock();
+
+	Ok((mainloop, context))
+}
+
+pub fn start(
+	internal_rx: cb_channel::Receiver<PAInternal>,
+	info_sx: mpsc::UnboundedSender<EntryIdentifier>,
+	actions_sx: ActionsSender,
+	server: Option<String>,
+) -> Result<()> {
+	let (mainloop, context) = connect(server.as_deref())?;
+
+	mainloop.borrow_mut().lock();
 
-	callbacks::subscribe(&context, info_sx.clone())?;
-	callbacks::request_current_state(context.clone(), info_sx.clone())?;
+	callbacks::subscribe(&context, info_sx.clone(), actions_sx.clone())?;
+	callbacks::request_current_state(context.clone(), info_sx.clone(), &actions_sx)?;
 
 	mainloop.borrow_mut().unlock();
 
 	debug!("[PAInterface] Actually starting our mainloop");
 
-	let mut monitors = Monitors::default();
-	let mut last_targets = HashMap::new();
-
 	while let Ok(msg) = internal_rx.recv() {
 		mainloop.borrow_mut().lock();
 
@@ -132,24 +137,16 @@ pub fn start(
 
 		match msg {
 			PAInternal::AskInfo(ident) => {
-				callbacks::request_info(ident, &context, info_sx.clone());
-			}
-			PAInternal::Tick => {
-				// remove failed monitors
-				monitors.filter(&mainloop, &context, &last_targets);
+				callbacks::request_info(ident, &context, info_sx.clone(), &actions_sx);
 			}
 			PAInternal::Command(cmd) => {
 				let cmd = cmd.deref();
-				if pa_actions::handle_command(cmd.clone(), &context, &info_sx).is_none() {
-					monitors.filter(&mainloop, &context, &HashMap::new());
+				if pa_actions::handle_command(cmd.clone(), &context, &info_sx, &actions_sx)
+					.is_none()
+				{
 					mainloop.borrow_mut().unlock();
 					break;
 				}
-
-				if let PulseAudioAction::CreateMonitors(mons) = cmd.clone() {
-					last_targets = mons;
-					monitors.filter(&mainloop, &context, &last_targets);
-				}
 			}
 		};
 		mainloop.borrow_mut().unlock();
@@ -11,12 +11,13 @@ use buffer::{Buffer, Pixel, Pixels};
 pub use errors::UIError;
 pub use rect::Rect;
 pub use scrollable::Scrollable;
-pub use util::{clean_terminal, entry_height, prepare_terminal};
+pub use util::{clean_terminal, prepare_terminal};
 use widgets::{BlockWidget, Widget};
 
 use crate::{
-	models::{PageType, RSState, Style, UIMode},
+	models::{server_label, PageType, RSState, Style, UIMode},
 	prelude::*,
+	VARIABLES,
 };
 
 pub async fn redraw<W: Write>(stdout: &mut W, state: &mut RSState) -> Result<()> {
@@ -52,7 +53,10 @@ pub async fn make_changes(state: &mut RSState) -> Result<()> {
 	}
 
 	if let UIMode::RetryIn(time) = state.ui_mode {
-		state.warning_text.text = format!("PulseAudio disconnected. Retrying in {time}...");
+		state.warning_text.text = match &(*VARIABLES).get().pa_servers[state.current_server] {
+			Some(server) => format!("PulseAudio ({server}) disconnected. Retrying in {time}..."),
+			None => format!("PulseAudio disconnected. Retrying in {time}..."),
+		};
 		state.warning_text.render(&mut state.ui.buffer)?;
 
 		return Ok(());
@@ -60,6 +64,7 @@ pub async fn make_changes(state: &mut RSState) -> Result<()> {
 
 	if state.redraw.resize {
 		state.ui.border.title_pixels = Some(gen_page_names(state));
+		state.ui.border.status = state.server_info.status_line();
 		state.ui.border.render(&mut state.ui.buffer)?;
 	}
 
@@ -106,7 +111,7 @@ pub async fn make_changes(state: &mut RSState) -> Result<()> {
 		}
 	}
 
-	if let Some(index) = state.redraw.peak_volume {
+	for &index in &state.redraw.peak_volumes {
 		if state
 			.page_entries
 			.visible_range(state.ui.entries_area.height)
@@ -116,8 +121,7 @@ pub async fn make_changes(state: &mut RSState) -> Result<()> {
 				.entries
 				.get_play_entry_mut(&state.page_entries.get(index).unwrap())
 			{
-				play.peak_volume_bar = play.peak_volume_bar.volume(play.peak);
-				play.peak_volume_bar.small_render(&mut state.ui.buffer)?;
+				play.render_meters(&mut state.ui.buffer, false)?;
 			}
 		}
 	}
@@ -125,7 +129,11 @@ pub async fn make_changes(state: &mut RSState) -> Result<()> {
 	match state.ui_mode {
 		UIMode::Help => state.help.render(&mut state.ui.buffer)?,
 		UIMode::ContextMenu => state.context_menu.render(&mut state.ui.buffer)?,
-		UIMode::InputVolumeValue => state.input_exact_volume.render(&mut state.ui.buffer)?,
+		UIMode::InputVolumeValue | UIMode::InputLatencyOffset(_, _) => {
+			state.input_exact_volume.render(&mut state.ui.buffer)?
+		}
+		UIMode::InputModule => state.input_text.render(&mut state.ui.buffer)?,
+		UIMode::ServerInfo | UIMode::Details(_) => state.info_panel.render(&mut state.ui.buffer)?,
 		_ => {}
 	};
 
@@ -151,6 +159,8 @@ impl Default for UI {
 				PageType::Output.to_string(),
 				PageType::Input.to_string(),
 				PageType::Cards.to_string(),
+				PageType::Modules.to_string(),
+				PageType::Clients.to_string(),
 			],
 		}
 	}
@@ -182,11 +192,12 @@ fn resize(state: &mut RSState) -> Result<()> {
 		};
 		ent.position = state.page_entries.lvls[i];
 
-		entry_area = entry_area.h(entry_height(ent.position));
+		let height = state.page_entries.element_height(i);
+		entry_area = entry_area.h(height);
 
 		ent.resize(entry_area)?;
 
-		entry_area.y += entry_height(ent.position);
+		entry_area.y += height;
 	}
 
 	state.context_menu.resize(state.ui.entries_area)?;
@@ -199,33 +210,72 @@ fn resize(state: &mut RSState) -> Result<()> {
 		}
 	}
 
+	if let UIMode::InputLatencyOffset(ident, _) = state.ui_mode {
+		if let Some(card) = state.entries.get_card_entry(&ident) {
+			state.input_exact_volume.resize(card.area)?;
+		}
+	}
+
+	state.input_text.resize(state.ui.entries_area)?;
+
+	if let UIMode::ServerInfo | UIMode::Details(_) = state.ui_mode {
+		state.info_panel.resize(state.ui.entries_area)?;
+	}
+
 	state.help.resize(state.ui.entries_area)?;
 
 	Ok(())
 }
 
 fn gen_page_names(state: &mut RSState) -> Pixels {
+	let current: i8 = state.current_page.into();
+
+	// server switcher is shown only when there is more than one server
+	let servers: Vec<String> = if state.servers.len() > 1 {
+		(0..state.servers.len()).map(server_label).collect()
+	} else {
+		Vec::new()
+	};
+
 	if state.ui.buffer.width as usize
-		> 2 + state.ui.pages_names.iter().map(|p| p.len()).sum::<usize>() + 6
+		> 2 + state.ui.pages_names.iter().map(|p| p.len()).sum::<usize>()
+			+ 3 * (state.ui.pages_names.len() - 1)
+			+ servers.iter().map(|s| s.len() + 3).sum::<usize>()
 	{
 		let style = |i: usize| {
-			if i as i8 == state.current_page.into() {
+			if i as i8 == current {
 				Style::Bold
 			} else {
 				Style::Muted
 			}
 		};
 
-		Pixels::default()
-			.string(style(0), &state.ui.pages_names[0])
-			.string(Style::Muted, " / ")
-			.string(style(1), &state.ui.pages_names[1])
-			.string(Style::Muted, " / ")
-			.string(style(2), &state.ui.pages_names[2])
+		let mut pixels = Pixels::default();
+		for (i, name) in state.ui.pages_names.iter().enumerate() {
+			if i > 0 {
+				pixels = pixels.string(Style::Muted, " / ");
+			}
+			pixels = pixels.string(style(i), name);
+		}
+		for (i, name) in servers.iter().enumerate() {
+			pixels = pixels.string(Style::Muted, if i == 0 { " | " } else { " / " });
+			pixels = pixels.string(
+				if i == state.current_server {
+					Style::Bold
+				} else {
+					Style::Muted
+				},
+				name,
+			);
+		}
+
+		pixels
 	} else {
-		Pixels::default().string(
-			Style::Bold,
-			&state.ui.pages_names[state.page_entries.selected()],
-		)
+		let pixels = Pixels::default().string(Style::Bold, &state.ui.pages_names[current as usize]);
+
+		match servers.get(state.current_server) {
+			Some(name) => pixels.string(Style::Muted, " | ").string(Style::Bold, name),
+			None => pixels,
+		}
 	}
 }
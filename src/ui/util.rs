@@ -5,7 +5,10 @@ use crossterm::{cursor::Hide, execute};
 use crate::entry::EntrySpaceLvl;
 
 pub fn entry_height(lvl: EntrySpaceLvl) -> u16 {
-	if lvl == EntrySpaceLvl::Card {
+	if matches!(
+		lvl,
+		EntrySpaceLvl::Card | EntrySpaceLvl::Module | EntrySpaceLvl::Client
+	) {
 		1
 	} else if lvl == EntrySpaceLvl::ParentNoChildren || lvl == EntrySpaceLvl::LastChild {
 		4
@@ -11,6 +11,8 @@ pub struct BlockWidget {
 	pub area: Rect,
 	pub title: Option<String>,
 	pub title_pixels: Option<Pixels>,
+	// text drawn on the right side of the top border
+	pub status: Option<String>,
 	pub clean_inside: bool,
 }
 
@@ -19,6 +21,7 @@ impl Default for BlockWidget {
 		Self {
 			title: None,
 			title_pixels: None,
+			status: None,
 			clean_inside: false,
 			area: Rect::default(),
 		}
@@ -65,6 +68,40 @@ impl Widget for BlockWidget {
 				}
 			}
 		}
+
+		let title_len = match (&self.title, self.title_pixels.as_mut()) {
+			(Some(title), _) => title.chars().count(),
+			(None, Some(title)) => title.iter_mut().count(),
+			(None, None) => 0,
+		};
+
+		if let Some(status) = &self.status {
+			// leave at least a bit of border between the title and status
+			let available = (self.area.width as usize).saturating_sub(title_len + 7);
+
+			let status: String = if status.chars().count() > available {
+				status
+					.chars()
+					.take(available.saturating_sub(1))
+					.chain(Some('…'))
+					.collect()
+			} else {
+				status.clone()
+			};
+			let status_len = status.chars().count();
+
+			// too little room to say anything useful
+			if status_len > 1 {
+				let start = self.area.width as usize - status_len - 2;
+				for (i, c) in status.chars().enumerate() {
+					if let Some(pixel) = top_border.get_mut(start + i) {
+						pixel.text = Some(c);
+						pixel.style = Style::Muted;
+					}
+				}
+			}
+		}
+
 		buffer.pixels(self.area.x, self.area.y, &top_border);
 
 		if self.clean_inside {
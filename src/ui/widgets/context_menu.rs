@@ -61,6 +61,8 @@ impl Widget for ContextMenu {
 				text,
 				if self.selected() == i {
 					Style::Inverted
+				} else if !self.options[i].is_available() {
+					Style::Muted
 				} else {
 					Style::Normal
 				},
@@ -1,14 +1,20 @@
 use std::cmp::min;
 
-use pulse::volume;
+use pulse::{channelmap::Position, volume};
 
 use crate::{
-	entry::{CardEntry, Entry, EntryKind, EntrySpaceLvl, HiddenStatus, PlayEntry},
+	config::VolumeDisplay,
+	entry::{
+		CardEntry, ClientEntry, Entry, EntryKind, EntrySpaceLvl, HiddenStatus, Meter, ModuleEntry,
+		PlayEntry,
+	},
 	prelude::*,
 	ui::{
-		widgets::{VolumeWidgetBorder, Widget},
+		widgets::{MeterWidget, VolumeWidget, VolumeWidgetBorder, Widget},
 		Buffer, Rect, Style, UIError,
 	},
+	util::{channel_volume_to_percent, format_stream_name},
+	VARIABLES,
 };
 
 impl Widget for Entry {
@@ -23,6 +29,8 @@ impl Widget for Entry {
 				play.resize(area)
 			}
 			EntryKind::CardEntry(card) => card.resize(area),
+			EntryKind::ModuleEntry(module) => module.resize(area),
+			EntryKind::ClientEntry(client) => client.resize(area),
 		}
 	}
 	fn render(&mut self, buffer: &mut Buffer) -> Result<()> {
@@ -38,6 +46,16 @@ impl Widget for Entry {
 
 				card.render(buffer)
 			}
+			EntryKind::ModuleEntry(module) => {
+				module.is_selected = self.is_selected;
+
+				module.render(buffer)
+			}
+			EntryKind::ClientEntry(client) => {
+				client.is_selected = self.is_selected;
+
+				client.render(buffer)
+			}
 		}
 	}
 }
@@ -81,6 +99,187 @@ impl PlayEntry {
 		Rect::new(self.area.x + self.offset(), self.area.y, w, 2)
 	}
 
+	fn peak_volume_y(&self) -> u16 {
+		match self.position {
+			EntrySpaceLvl::ParentNoChildren | EntrySpaceLvl::LastChild => {
+				self.area.y + self.area.height - 2
+			}
+			_ => self.area.y + self.area.height - 1,
+		}
+	}
+
+	// streams are named after the configured template, devices keep their name
+	fn display_name(&self) -> String {
+		if self.media_name.is_none() && self.binary.is_none() {
+			return self.name.clone();
+		}
+
+		format_stream_name(
+			&(*VARIABLES).get().stream_name,
+			&self.name,
+			self.media_name.as_deref(),
+			self.binary.as_deref(),
+		)
+	}
+
+	// peak hold is only drawn when it's enabled
+	fn update_meter_bars(&mut self) {
+		let settings = (*VARIABLES).get().meter;
+		let bar = |meter: Meter| {
+			(
+				Meter::fraction(meter.level, settings.dbfs),
+				if settings.hold_ms > 0 {
+					Some(Meter::fraction(meter.hold, settings.dbfs))
+				} else {
+					None
+				},
+			)
+		};
+
+		if self.stacked_meters() {
+			let channels = self.monitor_channels() as usize;
+			let meters = &self.meters;
+			let meter = |i: usize| meters.get(i).copied().unwrap_or_default();
+			for (row, widget) in self.channel_meter_bars.iter_mut().enumerate() {
+				let (upper, lower) = (meter(row * 2), meter(row * 2 + 1));
+
+				widget.max = Meter::bar_max(settings.dbfs);
+				widget.mute = self.mute;
+				widget.upper = bar(upper);
+				widget.lower = if row * 2 + 1 < channels {
+					Some(bar(lower))
+				} else {
+					None
+				};
+				widget.clip = upper.clip || lower.clip;
+			}
+			return;
+		}
+
+		let (level, hold) = bar(self.meter());
+		self.peak_volume_bar = self
+			.peak_volume_bar
+			.max(Meter::bar_max(settings.dbfs))
+			.volume(level)
+			.hold(hold)
+			.clip(self.meter().clip);
+		self.peak_volume_bar.mute = self.mute;
+	}
+
+	// `full` redraws the whole bars, otherwise only what changed since the last render
+	pub fn render_meters(&mut self, buffer: &mut Buffer, full: bool) -> Result<()> {
+		self.update_meter_bars();
+
+		if self.stacked_meters() {
+			for widget in &mut self.channel_meter_bars {
+				widget.render(buffer)?;
+			}
+		} else if full {
+			self.peak_volume_bar.render(buffer)?;
+		} else {
+			self.peak_volume_bar.small_render(buffer)?;
+		}
+
+		Ok(())
+	}
+
+	// the volume bar spans up to the configured max volume, but at least 100%
+	fn volume_scale(&self) -> f32 {
+		let max = (*VARIABLES).get().max_volume(self.device_name.as_deref());
+
+		max.max(100) as f32 / 100.0
+	}
+
+	// small "L──●──R" indicator, `None` if the channel map has no left/right
+	fn balance_indicator(&self) -> Option<String> {
+		if !self.channel_map.can_balance() {
+			return None;
+		}
+
+		let balance = self.volume.get_balance(&self.channel_map);
+		let pos = ((balance + 1.0) / 2.0 * 4.0).round() as usize;
+
+		Some(format!(
+			"L{}R",
+			(0..5)
+				.map(|i| if i == pos { "●" } else { "─" })
+				.collect::<String>()
+		))
+	}
+
+	// "current/configured ms", `None` while nothing is reported
+	fn latency_label(&self) -> Option<String> {
+		let latency = self.latency?;
+		let ms = |usec: u64| usec as f64 / 1000.0;
+
+		match latency.configured {
+			Some(configured) if configured > 0 => Some(format!(
+				"{:.1}/{:.1} ms",
+				ms(latency.current),
+				ms(configured)
+			)),
+			_ if latency.current > 0 => Some(format!("{:.1} ms", ms(latency.current))),
+			_ => None,
+		}
+	}
+
+	fn render_channels(&self, buffer: &mut Buffer, style: Style) -> Result<()> {
+		let text_area = self.play_entry_text_area();
+
+		for (i, (position, vol)) in self
+			.channel_map
+			.get()
+			.iter()
+			.zip(self.volume.get())
+			.enumerate()
+		{
+			let y = text_area.y + 2 + i as u16;
+			let percent = channel_volume_to_percent(*vol);
+
+			if self.is_volume_visible() {
+				let scale = self.volume_scale();
+				let mut bar = VolumeWidget::default()
+					.set_area(self.volume_bar.area.y(y))
+					.volume(percent as f32 / 100.0 / scale)
+					.max(scale)
+					.mute(self.mute)
+					.border(VolumeWidgetBorder::Single);
+
+				bar.render(buffer)?;
+			}
+
+			if text_area.width < 7 {
+				continue;
+			}
+
+			let perc = match (*VARIABLES).get().volume_display {
+				VolumeDisplay::Db => vol.print_db(),
+				_ => format!("{percent:>3}"),
+			};
+			let label = Position::to_pretty_string(*position)
+				.unwrap_or_default()
+				.chars()
+				.take((text_area.width as usize - 4).saturating_sub(perc.len()))
+				.collect::<String>();
+
+			let label_style = if self.is_selected && i == self.selected_channel {
+				Style::Inverted
+			} else {
+				style
+			};
+
+			buffer.string(text_area.x + 1, y, label, label_style);
+			buffer.string(
+				text_area.x + text_area.width - 2 - perc.len() as u16,
+				y,
+				perc,
+				style,
+			);
+		}
+
+		Ok(())
+	}
+
 	fn offset(&self) -> u16 {
 		match self.position {
 			EntrySpaceLvl::Parent | EntrySpaceLvl::ParentNoChildren => 2,
@@ -151,12 +350,7 @@ impl Widget for PlayEntry {
 			self.volume_bar = self.volume_bar.set_area(volume_area);
 		}
 
-		let y = match self.position {
-			EntrySpaceLvl::ParentNoChildren | EntrySpaceLvl::LastChild => {
-				self.area.y + self.area.height - 2
-			}
-			_ => self.area.y + self.area.height - 1,
-		};
+		let y = self.peak_volume_y();
 
 		self.peak_volume_bar = self.peak_volume_bar.set_area(Rect::new(
 			self.area.x + self.offset(),
@@ -165,6 +359,23 @@ impl Widget for PlayEntry {
 			1,
 		));
 
+		// stacked meters end on the row of the mono one
+		self.channel_meter_bars = if self.stacked_meters() {
+			let rows = self.meter_rows();
+			(0..rows)
+				.map(|row| {
+					MeterWidget::default().set_area(Rect::new(
+						self.area.x + self.offset(),
+						y + 1 + row - rows,
+						self.area.width - self.offset() - 1,
+						1,
+					))
+				})
+				.collect()
+		} else {
+			Vec::new()
+		};
+
 		Ok(())
 	}
 
@@ -187,15 +398,27 @@ impl Widget for PlayEntry {
 		};
 
 		let text_area = self.play_entry_text_area();
-		let short_name = self
-			.name
-			.chars()
-			.take(if text_area.width > 2 {
-				text_area.width as usize - 2
-			} else {
-				0
-			})
-			.collect::<String>();
+		let name = if self.is_default {
+			format!("● {}", self.display_name())
+		} else {
+			self.display_name()
+		};
+		let mut name_width = (text_area.width as usize).saturating_sub(2);
+
+		// latency goes to the end of the name line, if the name keeps a few characters
+		if let Some(latency) = self.latency_label() {
+			if name_width >= latency.len() + 10 {
+				name_width -= latency.len() + 1;
+				buffer.string(
+					text_area.x + (name_width + 1) as u16,
+					text_area.y,
+					latency,
+					style,
+				);
+			}
+		}
+
+		let short_name = name.chars().take(name_width).collect::<String>();
 
 		buffer.string(text_area.x, text_area.y, short_name, name_style);
 
@@ -205,9 +428,11 @@ impl Widget for PlayEntry {
 
 		if self.is_volume_visible() {
 			let volume_area = self.volume_bar.area;
+			let scale = self.volume_scale();
 			self.volume_bar = self
 				.volume_bar
-				.volume(vol_percent as f32 / 150.0)
+				.volume(vol_percent as f32 / 100.0 / scale)
+				.max(scale)
 				.mute(self.mute)
 				.border(VolumeWidgetBorder::Upper);
 
@@ -240,31 +465,51 @@ impl Widget for PlayEntry {
 			}
 		}
 
-		let vol_perc = format!("  {vol_percent}");
-		let vol_perc = String::from(&vol_perc[vol_perc.len() - 3..vol_perc.len()]);
-		let vol_db = self.volume.avg().print_db();
-
-		if vol_db.len() + vol_perc.len() <= text_area.width as usize + 3 {
-			let vol_str = format!(
-				"{}{}{}",
-				vol_db,
-				(0..text_area.width as usize - 3 - vol_perc.len() - vol_db.len())
-					.map(|_| " ")
-					.collect::<String>(),
-				vol_perc
-			);
+		let display = (*VARIABLES).get().volume_display;
+		let vol_perc = if display == VolumeDisplay::Db {
+			String::new()
+		} else {
+			format!("{vol_percent:>3}")
+		};
+		let vol_db = if display == VolumeDisplay::Percent {
+			String::new()
+		} else {
+			self.volume.avg().print_db()
+		};
+
+		if vol_db.len() + vol_perc.len() + 3 <= text_area.width as usize {
+			let gap = (text_area.width as usize).saturating_sub(3 + vol_perc.len() + vol_db.len());
+			let vol_str = format!("{}{}{}", vol_db, " ".repeat(gap), vol_perc);
 
 			buffer.string(text_area.x + 1, text_area.y + 1, vol_str, style);
+
+			if let Some(balance) = self.balance_indicator() {
+				let len = balance.chars().count();
+				if gap >= len + 2 {
+					buffer.string(
+						text_area.x + 1 + (vol_db.len() + (gap - len) / 2) as u16,
+						text_area.y + 1,
+						balance,
+						style,
+					);
+				}
+			}
 		}
 
-		self.peak_volume_bar.mute = self.mute;
-		self.peak_volume_bar.render(buffer)?;
+		if self.expanded {
+			self.render_channels(buffer, style)?;
+		}
+
+		self.render_meters(buffer, true)?;
+
+		let peak_y = self.peak_volume_y();
 
 		match self.position {
 			EntrySpaceLvl::Parent => {
 				buffer.string(self.area.x, self.area.y, "▼".to_string(), style);
-				buffer.string(self.area.x, self.area.y + 1, "│".to_string(), style);
-				buffer.string(self.area.x, self.area.y + 2, "│".to_string(), style);
+				for y in (self.area.y + 1)..(self.area.y + self.area.height) {
+					buffer.string(self.area.x, y, "│".to_string(), style);
+				}
 			}
 			EntrySpaceLvl::ParentNoChildren => match self.hidden {
 				HiddenStatus::HiddenKids => {
@@ -276,14 +521,16 @@ impl Widget for PlayEntry {
 				_ => {}
 			},
 			EntrySpaceLvl::MidChild => {
-				buffer.string(self.area.x, self.area.y, "│".to_string(), style);
-				buffer.string(self.area.x, self.area.y + 1, "│".to_string(), style);
-				buffer.string(self.area.x, self.area.y + 2, "├───".to_string(), style);
+				for y in self.area.y..peak_y {
+					buffer.string(self.area.x, y, "│".to_string(), style);
+				}
+				buffer.string(self.area.x, peak_y, "├───".to_string(), style);
 			}
 			EntrySpaceLvl::LastChild => {
-				buffer.string(self.area.x, self.area.y, "│".to_string(), style);
-				buffer.string(self.area.x, self.area.y + 1, "│".to_string(), style);
-				buffer.string(self.area.x, self.area.y + 2, "└───".to_string(), style);
+				for y in self.area.y..peak_y {
+					buffer.string(self.area.x, y, "│".to_string(), style);
+				}
+				buffer.string(self.area.x, peak_y, "└───".to_string(), style);
 			}
 			_ => {}
 		};
@@ -291,3 +538,92 @@ impl Widget for PlayEntry {
 		Ok(())
 	}
 }
+
+impl Widget for ModuleEntry {
+	fn resize(&mut self, area: Rect) -> Result<()> {
+		self.area = area;
+		Ok(())
+	}
+
+	fn render(&mut self, buffer: &mut Buffer) -> Result<()> {
+		render_row(
+			buffer,
+			self.area,
+			self.is_selected,
+			&self.name,
+			self.argument.as_deref(),
+		);
+
+		Ok(())
+	}
+}
+
+impl Widget for ClientEntry {
+	fn resize(&mut self, area: Rect) -> Result<()> {
+		self.area = area;
+		Ok(())
+	}
+
+	fn render(&mut self, buffer: &mut Buffer) -> Result<()> {
+		let mut details = Vec::new();
+		if let Some(binary) = &self.binary {
+			details.push(binary.clone());
+		}
+		if let Some(pid) = &self.pid {
+			details.push(format!("pid {pid}"));
+		}
+		details.push(match self.streams {
+			1 => "1 stream".to_string(),
+			n => format!("{n} streams"),
+		});
+
+		render_row(
+			buffer,
+			self.area,
+			self.is_selected,
+			&self.name,
+			Some(&details.join(", ")),
+		);
+
+		Ok(())
+	}
+}
+
+// name on the left, muted details right aligned in what's left of the line
+fn render_row(
+	buffer: &mut Buffer,
+	area: Rect,
+	is_selected: bool,
+	name: &str,
+	details: Option<&str>,
+) {
+	buffer.rect(area, ' ', Style::Normal);
+
+	let name_style = if is_selected {
+		Style::Inverted
+	} else {
+		Style::Normal
+	};
+
+	let name = name
+		.chars()
+		.take((area.width / 2).into())
+		.collect::<String>();
+	let name_len = name.chars().count() as u16;
+
+	buffer.string(area.x, area.y, name, name_style);
+
+	if let Some(details) = details {
+		let details = details
+			.chars()
+			.take(area.width.saturating_sub(name_len + 2).into())
+			.collect::<String>();
+
+		buffer.string(
+			area.x + area.width - details.chars().count() as u16,
+			area.y,
+			details,
+			Style::Muted,
+		);
+	}
+}
@@ -0,0 +1,125 @@
+use super::{ToolWindowWidget, Widget};
+use crate::{
+	prelude::*,
+	ui::{Buffer, Rect, Style, UIError},
+};
+
+// window showing a list of labeled values, e.g. server info or entry details
+#[derive(Clone, Default)]
+pub struct InfoWidget {
+	pub window: ToolWindowWidget,
+	pub lines: Vec<(String, String)>,
+	scroll: usize,
+}
+
+impl InfoWidget {
+	pub fn set_lines(&mut self, lines: Vec<(String, String)>) {
+		self.lines = lines;
+		self.scroll = self.scroll.min(self.max_scroll());
+	}
+
+	pub fn reset_scroll(&mut self) {
+		self.scroll = 0;
+	}
+
+	pub fn up(&mut self, how_much: usize) {
+		self.scroll = self.scroll.saturating_sub(how_much);
+	}
+
+	pub fn down(&mut self, how_much: usize) {
+		self.scroll = (self.scroll + how_much).min(self.max_scroll());
+	}
+
+	fn inner_height(&self) -> usize {
+		self.window
+			.area
+			.height
+			.saturating_sub(self.window.padding.1 * 2) as usize
+	}
+
+	fn max_scroll(&self) -> usize {
+		self.lines.len().saturating_sub(self.inner_height())
+	}
+
+	fn label_width(&self) -> u16 {
+		self.lines
+			.iter()
+			.map(|(l, _)| l.chars().count())
+			.max()
+			.unwrap_or(0) as u16
+			+ 2
+	}
+}
+
+impl Widget for InfoWidget {
+	fn resize(&mut self, area: Rect) -> Result<()> {
+		if area.height < 3 || area.width < 10 {
+			return Err(UIError::TerminalTooSmall.into());
+		}
+
+		let longest_value = self
+			.lines
+			.iter()
+			.map(|(_, v)| v.chars().count())
+			.max()
+			.unwrap_or(0) as u16;
+
+		self.window.padding.0 = if area.width < 50 { 1 } else { 3 };
+		self.window.padding.1 = if area.height < 14 { 1 } else { 2 };
+
+		self.window.inner_width = self.label_width() + longest_value;
+		self.window.inner_height = self.lines.len() as u16;
+
+		self.window.resize(area)?;
+
+		self.scroll = self.scroll.min(self.max_scroll());
+
+		Ok(())
+	}
+
+	fn render(&mut self, buffer: &mut Buffer) -> Result<()> {
+		self.window.render(buffer)?;
+
+		let x = self.window.area.x + self.window.padding.0;
+		let y = self.window.area.y + self.window.padding.1;
+		let width = self
+			.window
+			.area
+			.width
+			.saturating_sub(self.window.padding.0 * 2);
+		let height = self.inner_height();
+		let label_width = self.label_width();
+
+		for (i, (label, value)) in self.lines.iter().skip(self.scroll).take(height).enumerate() {
+			buffer.string(
+				x,
+				y + i as u16,
+				label.chars().take(width as usize).collect(),
+				Style::Bold,
+			);
+
+			if width > label_width {
+				buffer.string(
+					x + label_width,
+					y + i as u16,
+					value.chars().take((width - label_width) as usize).collect(),
+					Style::Normal,
+				);
+			}
+		}
+
+		if self.scroll > 0 {
+			buffer.string(x + width / 2, y - 1, "▲".to_string(), Style::Normal);
+		}
+		if self.scroll < self.max_scroll() {
+			buffer.string(
+				x + width / 2,
+				y + height as u16,
+				"▼".to_string(),
+				Style::Normal,
+			);
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,108 @@
+use super::{volume::segments, VolumeWidgetBorder, Widget};
+use crate::{
+	prelude::*,
+	ui::{Buffer, Pixel, Rect, Style, UIError},
+};
+
+// two channel meters stacked in one row, the upper one drawn in the upper half of each cell
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MeterWidget {
+	pub area: Rect,
+	// level and peak hold of each channel, 0.0 - 1.0 of the bar
+	pub upper: (f32, Option<f32>),
+	pub lower: Option<(f32, Option<f32>)>,
+	pub clip: bool,
+	pub mute: bool,
+	// the red segment starts at 1 / `max` of the bar
+	pub max: f32,
+}
+
+impl MeterWidget {
+	pub fn default() -> Self {
+		Self {
+			area: Rect::default(),
+			upper: (0.0, None),
+			lower: None,
+			clip: false,
+			mute: false,
+			max: 1.0,
+		}
+	}
+
+	pub fn set_area(mut self, area: Rect) -> Self {
+		self.area = area;
+		self
+	}
+
+	fn index(&self, value: f32) -> u16 {
+		let width = self.area.width - 2;
+		((value * width as f32).floor() as u16).min(width.saturating_sub(1))
+	}
+
+	fn text(&self, i: u16) -> char {
+		let width = self.area.width - 2;
+		let filled = |(level, _): (f32, Option<f32>)| i < (level * width as f32).floor() as u16;
+		let held = |(_, hold): (f32, Option<f32>)| hold.map(|h| self.index(h)) == Some(i);
+
+		let upper = filled(self.upper);
+		let lower = self.lower.is_some_and(filled);
+
+		match (upper, lower) {
+			(true, true) => '█',
+			(true, false) => '▀',
+			(false, true) => '▄',
+			_ => match (held(self.upper), self.lower.is_some_and(held)) {
+				(true, true) => '│',
+				(true, false) => '╵',
+				(false, true) => '╷',
+				_ => '-',
+			},
+		}
+	}
+}
+
+impl Widget for MeterWidget {
+	fn resize(&mut self, area: Rect) -> Result<()> {
+		if area.width < 3 || area.height < 1 {
+			return Err(UIError::TerminalTooSmall.into());
+		}
+
+		self.area = area;
+
+		Ok(())
+	}
+
+	fn render(&mut self, buffer: &mut Buffer) -> Result<()> {
+		VolumeWidgetBorder::Single.render(buffer, &self.area);
+
+		let segments = segments(self.area.width - 2, self.max);
+
+		let pixels: Vec<Pixel> = (0..(self.area.width - 2))
+			.map(|i| Pixel {
+				text: Some(self.text(i)),
+				style: if self.mute {
+					Style::Muted
+				} else if i < segments.0 {
+					Style::Green
+				} else if i < segments.1 {
+					Style::Orange
+				} else {
+					Style::Red
+				},
+			})
+			.collect();
+
+		buffer.pixels(self.area.x + 1, self.area.y, &pixels.into());
+
+		if self.clip {
+			buffer.string(
+				self.area.x + self.area.width - 1,
+				self.area.y,
+				"!".to_string(),
+				Style::Red,
+			);
+		}
+
+		Ok(())
+	}
+}
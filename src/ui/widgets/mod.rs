@@ -2,6 +2,9 @@ mod block;
 mod context_menu;
 mod entry;
 mod help;
+mod info;
+mod meter;
+mod text_input;
 mod tool_window;
 mod volume;
 mod volume_input;
@@ -9,6 +12,9 @@ mod warning_text;
 
 pub use block::BlockWidget;
 pub use help::HelpWidget;
+pub use info::InfoWidget;
+pub use meter::MeterWidget;
+pub use text_input::TextInputWidget;
 pub use tool_window::ToolWindowWidget;
 pub use volume::{VolumeWidget, VolumeWidgetBorder};
 pub use volume_input::VolumeInputWidget;
@@ -0,0 +1,78 @@
+use std::cmp::min;
+
+use super::{BlockWidget, Widget};
+use crate::{
+	models::Style,
+	prelude::*,
+	ui::{Buffer, Rect},
+};
+
+#[derive(Clone)]
+pub struct TextInputWidget {
+	pub value: String,
+	pub cursor: usize,
+	pub window: BlockWidget,
+}
+
+impl Default for TextInputWidget {
+	fn default() -> Self {
+		Self {
+			value: "".to_string(),
+			cursor: 0,
+			window: BlockWidget::default().clean_inside(true),
+		}
+	}
+}
+
+impl TextInputWidget {
+	pub fn title(mut self, title: &str) -> Self {
+		self.window.title = Some(title.to_string());
+		self
+	}
+}
+
+impl Widget for TextInputWidget {
+	fn resize(&mut self, area: Rect) -> Result<()> {
+		let width = min(60, area.width);
+		let area = Rect::new(
+			area.x + (area.width - width) / 2,
+			area.y + area.height.saturating_sub(3) / 2,
+			width,
+			3,
+		);
+		self.window.resize(area)?;
+		Ok(())
+	}
+
+	fn render(&mut self, buffer: &mut Buffer) -> Result<()> {
+		self.window.render(buffer)?;
+
+		let width = self.window.area.width as usize - 4;
+		let cursor = self.cursor;
+
+		// scroll the text so that the cursor is always visible
+		let skip = (cursor + 1).saturating_sub(width);
+
+		let text = self
+			.value
+			.chars()
+			.skip(skip)
+			.take(width)
+			.collect::<String>();
+
+		let x = self.window.area.x + 2;
+		let y = self.window.area.y + 1;
+
+		buffer.string(x, y, text, Style::Normal);
+
+		let under_cursor = self.value.chars().nth(cursor).unwrap_or(' ');
+		buffer.string(
+			x + (cursor - skip) as u16,
+			y,
+			under_cursor.to_string(),
+			Style::Inverted,
+		);
+
+		Ok(())
+	}
+}
@@ -19,6 +19,13 @@ pub struct VolumeWidget {
 	pub border: VolumeWidgetBorder,
 	pub area: Rect,
 	pub mute: bool,
+	// the full bar corresponds to `max` * 100%
+	pub max: f32,
+	// peak hold marker and clip indicator of meters
+	pub hold: Option<f32>,
+	pub last_hold: Option<f32>,
+	pub clip: bool,
+	pub last_clip: bool,
 }
 
 impl VolumeWidget {
@@ -29,6 +36,11 @@ impl VolumeWidget {
 			border: VolumeWidgetBorder::Single,
 			area: Rect::default(),
 			mute: false,
+			max: 1.5,
+			hold: None,
+			last_hold: None,
+			clip: false,
+			last_clip: false,
 		}
 	}
 
@@ -48,17 +60,56 @@ impl VolumeWidget {
 		self
 	}
 
+	pub fn max(mut self, max: f32) -> Self {
+		self.max = max;
+		self
+	}
+
+	pub fn hold(mut self, hold: Option<f32>) -> Self {
+		self.last_hold = self.hold;
+		self.hold = hold;
+		self
+	}
+
+	pub fn clip(mut self, clip: bool) -> Self {
+		self.last_clip = self.clip;
+		self.clip = clip;
+		self
+	}
+
 	pub fn set_area(mut self, area: Rect) -> Self {
 		self.area = area;
 		self
 	}
 
 	fn get_segments(&self) -> (u16, u16, u16) {
-		let width = self.area.width;
-		let third = (0.34 * (width - 2) as f32).floor() as u16;
-		let last = width - 2 - third * 2;
+		segments(self.area.width - 2, self.max)
+	}
+
+	fn index(&self, value: Option<f32>) -> Option<u16> {
+		let width = self.area.width - 2;
+		value.map(|v| ((v * width as f32).floor() as u16).min(width.saturating_sub(1)))
+	}
 
-		(third, third * 2, third * 2 + last)
+	fn pixel(&self, i: u16, filled: u16, segments: (u16, u16, u16)) -> Pixel {
+		Pixel {
+			text: if i < filled {
+				Some('▮')
+			} else if Some(i) == self.index(self.hold) {
+				Some('|')
+			} else {
+				Some('-')
+			},
+			style: if self.mute {
+				Style::Muted
+			} else if i < segments.0 {
+				Style::Green
+			} else if i < segments.1 {
+				Style::Orange
+			} else {
+				Style::Red
+			},
+		}
 	}
 
 	pub fn small_render(&mut self, buffer: &mut Buffer) -> Result<()> {
@@ -70,24 +121,39 @@ impl VolumeWidget {
 		let segments = self.get_segments();
 
 		let pixels: Vec<Pixel> = (smaller..greater)
-			.map(|i| Pixel {
-				text: if i < filled { Some('▮') } else { Some('-') },
-				style: if self.mute {
-					Style::Muted
-				} else if i < segments.0 {
-					Style::Green
-				} else if i < segments.1 {
-					Style::Orange
-				} else {
-					Style::Red
-				},
-			})
+			.map(|i| self.pixel(i, filled, segments))
 			.collect();
 
 		buffer.pixels(self.area.x + 1 + smaller, self.area.y, &pixels.into());
 
+		// the hold marker moves independently of the level
+		for i in vec![self.index(self.last_hold), self.index(self.hold)]
+			.into_iter()
+			.flatten()
+			.filter(|i| *i < smaller || *i >= greater)
+		{
+			let pixels: Vec<Pixel> = vec![self.pixel(i, filled, segments)];
+			buffer.pixels(self.area.x + 1 + i, self.area.y, &pixels.into());
+		}
+
+		if self.clip != self.last_clip {
+			self.border.render(buffer, &self.area);
+			self.render_clip(buffer);
+		}
+
 		Ok(())
 	}
+
+	fn render_clip(&self, buffer: &mut Buffer) {
+		if self.clip {
+			buffer.string(
+				self.area.x + self.area.width - 1,
+				self.area.y,
+				"!".to_string(),
+				Style::Red,
+			);
+		}
+	}
 }
 
 impl Widget for VolumeWidget {
@@ -107,28 +173,26 @@ impl Widget for VolumeWidget {
 		let segments = self.get_segments();
 
 		let pixels: Vec<Pixel> = (0..(self.area.width - 2))
-			.map(|i| Pixel {
-				text: if i < filled { Some('▮') } else { Some('-') },
-				style: if self.mute {
-					Style::Muted
-				} else if i < segments.0 {
-					Style::Green
-				} else if i < segments.1 {
-					Style::Orange
-				} else {
-					Style::Red
-				},
-			})
+			.map(|i| self.pixel(i, filled, segments))
 			.collect();
 
 		buffer.pixels(self.area.x + 1, self.area.y, &pixels.into());
 
+		self.render_clip(buffer);
+
 		Ok(())
 	}
 }
 
+// green up to half of 100%, orange up to 100%, red above that
+pub(super) fn segments(width: u16, max: f32) -> (u16, u16, u16) {
+	let normal = ((width as f32 / max.max(1.0)).round() as u16).min(width);
+
+	(normal / 2, normal, width)
+}
+
 impl VolumeWidgetBorder {
-	fn render(&mut self, buffer: &mut Buffer, area: &Rect) {
+	pub(super) fn render(&mut self, buffer: &mut Buffer, area: &Rect) {
 		if *self == VolumeWidgetBorder::None {
 			return;
 		}
@@ -1,7 +1,11 @@
 use pulse::volume;
 
 pub fn volume_to_percent(volume: volume::ChannelVolumes) -> u16 {
-	let avg = volume.avg().0;
+	channel_volume_to_percent(volume.avg())
+}
+
+pub fn channel_volume_to_percent(volume: volume::Volume) -> u16 {
+	let avg = volume.0;
 
 	let base_delta = (volume::Volume::NORMAL.0 as f32 - volume::Volume::MUTED.0 as f32) / 100.0;
 
@@ -15,15 +19,93 @@ pub fn percent_to_volume(target_percent: i16) -> u32 {
 		volume::Volume::MUTED.0
 	} else if target_percent == 100 {
 		volume::Volume::NORMAL.0
-	} else if target_percent >= 150 {
-		(volume::Volume::NORMAL.0 as f32 * 1.5) as u32
 	} else if target_percent < 100 {
 		volume::Volume::MUTED.0 + target_percent as u32 * base_delta as u32
 	} else {
-		volume::Volume::NORMAL.0 + (target_percent - 100) as u32 * base_delta as u32
+		(volume::Volume::NORMAL.0 + (target_percent - 100) as u32 * base_delta as u32)
+			.min(volume::Volume::MAX.0)
 	}
 }
 
+// `-6dB` and `+3dB` are absolute values in decibels, `45%` and `45` absolute
+// percentages, and `+5`/`-5` change the current volume by that many percent
+pub fn parse_volume_input(
+	input: &str,
+	current: volume::Volume,
+	max_percent: i16,
+) -> Option<volume::Volume> {
+	let input = input.trim().to_lowercase();
+
+	let target = if let Some(db) = input.strip_suffix("db") {
+		volume::Volume::from(volume::VolumeDB(db.trim().parse::<f64>().ok()?))
+	} else if let Some(percent) = input.strip_suffix('%') {
+		volume::Volume(percent_to_volume(percent.trim().parse::<i16>().ok()?))
+	} else if input.starts_with('+') || input.starts_with('-') {
+		let delta = input.parse::<i32>().ok()?;
+		let percent =
+			(channel_volume_to_percent(current) as i32 + delta).clamp(0, max_percent.max(0) as i32);
+
+		volume::Volume(percent_to_volume(percent as i16))
+	} else {
+		volume::Volume(percent_to_volume(input.parse::<i16>().ok()?))
+	};
+
+	let max = volume::Volume(percent_to_volume(max_percent));
+
+	Some(if target > max { max } else { target })
+}
+
+// fills `{app}`, `{media}` and `{binary}` placeholders, separators left over
+// from missing values are trimmed
+pub fn format_stream_name(
+	template: &str,
+	app: &str,
+	media: Option<&str>,
+	binary: Option<&str>,
+) -> String {
+	let media = media.filter(|m| *m != app).unwrap_or_default();
+
+	let name = template
+		.replace("{app}", app)
+		.replace("{media}", media)
+		.replace("{binary}", binary.unwrap_or_default());
+
+	let name = name.trim_matches(|c: char| c.is_whitespace() || ":-|,".contains(c));
+
+	if name.is_empty() {
+		app.to_string()
+	} else {
+		name.to_string()
+	}
+}
+
+// simple glob, `*` matches any (possibly empty) sequence of characters
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+	let parts: Vec<&str> = pattern.split('*').collect();
+
+	if parts.len() == 1 {
+		return pattern == name;
+	}
+
+	let first = parts[0];
+	let last = parts[parts.len() - 1];
+
+	if name.len() < first.len() + last.len() || !name.starts_with(first) || !name.ends_with(last) {
+		return false;
+	}
+
+	let mut rest = &name[first.len()..name.len() - last.len()];
+
+	for part in &parts[1..parts.len() - 1] {
+		match rest.find(part) {
+			Some(i) => rest = &rest[i + part.len()..],
+			None => return false,
+		}
+	}
+
+	true
+}
+
 #[macro_export]
 macro_rules! unwrap_or_return {
 	($x:expr, $y:expr) => {
@@ -66,3 +148,106 @@ macro_rules! warn {
         log::warn!("[{}] {}", LOGGING_MODULE, format!($($x),*));
     }
 }
+
+#[cfg(test)]
+mod tests {
+	use pulse::volume::{Volume, VolumeDB};
+
+	use super::*;
+
+	fn percent(p: i16) -> Volume {
+		Volume(percent_to_volume(p))
+	}
+
+	#[test]
+	fn volume_input_in_decibels() {
+		assert_eq!(
+			parse_volume_input("-6dB", percent(100), 150),
+			Some(Volume::from(VolumeDB(-6.0)))
+		);
+		assert_eq!(
+			parse_volume_input("+3dB", percent(100), 150),
+			Some(Volume::from(VolumeDB(3.0)))
+		);
+	}
+
+	#[test]
+	fn volume_input_in_percent() {
+		assert_eq!(
+			parse_volume_input("45%", percent(80), 150),
+			Some(percent(45))
+		);
+		assert_eq!(
+			parse_volume_input("45", percent(80), 150),
+			Some(percent(45))
+		);
+	}
+
+	#[test]
+	fn relative_volume_input() {
+		assert_eq!(
+			parse_volume_input("+5", percent(50), 150),
+			Some(percent(55))
+		);
+		assert_eq!(
+			parse_volume_input("-5", percent(50), 150),
+			Some(percent(45))
+		);
+	}
+
+	#[test]
+	fn volume_input_is_clamped() {
+		assert_eq!(
+			parse_volume_input("200", percent(50), 150),
+			Some(percent(150))
+		);
+		assert_eq!(
+			parse_volume_input("+60", percent(100), 150),
+			Some(percent(150))
+		);
+		assert_eq!(
+			parse_volume_input("-60", percent(50), 150),
+			Some(percent(0))
+		);
+	}
+
+	#[test]
+	fn huge_relative_volume_input_does_not_overflow() {
+		assert_eq!(
+			parse_volume_input("+32767", percent(100), 150),
+			Some(percent(150))
+		);
+		assert_eq!(
+			parse_volume_input("-32768", percent(100), 150),
+			Some(percent(0))
+		);
+		assert_eq!(
+			parse_volume_input("+99999999", percent(100), 150),
+			Some(percent(150))
+		);
+	}
+
+	#[test]
+	fn glob_patterns() {
+		assert!(matches_pattern("bluez_sink.*", "bluez_sink.00_11_22"));
+		assert!(matches_pattern("*.monitor", "alsa_output.pci.monitor"));
+		assert!(matches_pattern(
+			"alsa_*pci*stereo",
+			"alsa_output.pci-0000.analog-stereo"
+		));
+		assert!(matches_pattern("*", ""));
+		assert!(matches_pattern("a*a", "aa"));
+		assert!(matches_pattern("exact", "exact"));
+
+		assert!(!matches_pattern("exact", "exact.not"));
+		assert!(!matches_pattern("bluez_sink.*", "alsa_output.pci"));
+		assert!(!matches_pattern("a*a", "a"));
+		assert!(!matches_pattern("a*b*c", "acb"));
+	}
+
+	#[test]
+	fn invalid_volume_input() {
+		assert_eq!(parse_volume_input("loud", percent(100), 150), None);
+		assert_eq!(parse_volume_input("", percent(100), 150), None);
+	}
+}